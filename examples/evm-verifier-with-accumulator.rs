@@ -444,7 +444,9 @@ mod aggregation {
                     let KzgAccumulator { lhs, rhs } =
                         aggregate(&self.svk, &loader, &self.snarks, self.as_proof());
 
-                    Ok((lhs.assigned(), rhs.assigned()))
+                    let [lhs, rhs] = [lhs.assigned(), rhs.assigned()]
+                        .map(|assigned| assigned.map_err(|_| plonk::Error::Synthesis));
+                    Ok((lhs?, rhs?))
                 },
             )?;
 
@@ -1,3 +1,8 @@
+#[cfg(feature = "cost_estimate")]
+use crate::{
+    util::arithmetic::{CurveAffine, PrimeField},
+    Protocol,
+};
 use std::ops::Add;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -6,6 +11,9 @@ pub struct Cost {
     pub num_commitment: usize,
     pub num_evaluation: usize,
     pub num_msm: usize,
+    /// Number of terms of each msm the verification ends up evaluating.
+    pub msm_arities: Vec<usize>,
+    pub num_pairings: usize,
 }
 
 impl Cost {
@@ -20,6 +28,8 @@ impl Cost {
             num_commitment,
             num_evaluation,
             num_msm,
+            msm_arities: Vec::new(),
+            num_pairings: 0,
         }
     }
 }
@@ -28,12 +38,16 @@ impl Add<Cost> for Cost {
     type Output = Cost;
 
     fn add(self, rhs: Cost) -> Self::Output {
-        Cost::new(
-            self.num_instance + rhs.num_instance,
-            self.num_commitment + rhs.num_commitment,
-            self.num_evaluation + rhs.num_evaluation,
-            self.num_msm + rhs.num_msm,
-        )
+        Cost {
+            msm_arities: [self.msm_arities, rhs.msm_arities].concat(),
+            num_pairings: self.num_pairings + rhs.num_pairings,
+            ..Cost::new(
+                self.num_instance + rhs.num_instance,
+                self.num_commitment + rhs.num_commitment,
+                self.num_evaluation + rhs.num_evaluation,
+                self.num_msm + rhs.num_msm,
+            )
+        }
     }
 }
 
@@ -42,3 +56,39 @@ pub trait CostEstimation<T> {
 
     fn estimate_cost(input: &Self::Input) -> Cost;
 }
+
+/// Static estimate of a proof and its verification, derived from a verifier's
+/// [`Cost`] before any synthesis.
+#[cfg(feature = "cost_estimate")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostEstimate {
+    /// Size of the proof with compressed points, as read by the halo2
+    /// transcript.
+    pub proof_bytes: usize,
+    /// Total number of terms over all msms.
+    pub num_msm: usize,
+    /// Number of terms of each msm evaluated, e.g. the two sides of a KZG
+    /// accumulator.
+    pub msm_arities: Vec<usize>,
+    /// Number of pairings computed by the decider.
+    pub num_pairings: usize,
+}
+
+/// Estimates the proof size and verification cost of verifier `V` for
+/// `protocol` from its [`CostEstimation`], without synthesizing anything.
+#[cfg(feature = "cost_estimate")]
+pub fn estimate<C, T, V>(protocol: &Protocol<C>) -> CostEstimate
+where
+    C: CurveAffine,
+    V: CostEstimation<T, Input = Protocol<C>>,
+{
+    let cost = V::estimate_cost(protocol);
+    let ec_point_bytes = C::Repr::default().as_ref().len();
+    let scalar_bytes = <C::Scalar as PrimeField>::Repr::default().as_ref().len();
+    CostEstimate {
+        proof_bytes: cost.num_commitment * ec_point_bytes + cost.num_evaluation * scalar_bytes,
+        num_msm: cost.num_msm,
+        msm_arities: cost.msm_arities,
+        num_pairings: cost.num_pairings,
+    }
+}
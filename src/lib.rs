@@ -16,13 +16,23 @@ pub enum Error {
     InvalidQuery(util::protocol::Query),
     InvalidChallenge(usize),
     AssertionFailure(String),
+    AssertionFailureDetailed { annotation: String, detail: String },
+    ChipError { op: &'static str, source: String },
+    InvalidInput { op: &'static str, detail: String },
     Transcript(std::io::ErrorKind, String),
+    Parse(String),
+    RecursionLimit(usize),
 }
 
 #[derive(Clone, Debug)]
 pub struct Protocol<C: util::arithmetic::CurveAffine> {
     // Common description
     pub domain: util::arithmetic::Domain<C::Scalar>,
+    /// Number of blinded rows at the end of the domain. The vanishing
+    /// polynomial still spans the whole domain, so blinded rows are excluded
+    /// by the `l_last` and `l_blind` Lagrange terms compiled into `quotient`
+    /// rather than by the verifier's vanishing computation.
+    pub num_blinding_factors: usize,
     pub preprocessed: Vec<C>,
     pub num_instance: Vec<usize>,
     pub num_witness: Vec<usize>,
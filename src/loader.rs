@@ -1,6 +1,6 @@
 use crate::{
     util::{
-        arithmetic::{CurveAffine, FieldOps, PrimeField},
+        arithmetic::{CurveAffine, Domain, Field, FieldOps, PrimeField, Rotation},
         Itertools,
     },
     Error,
@@ -39,6 +39,10 @@ pub trait LoadedScalar<F: PrimeField>: Clone + Debug + PartialEq + FieldOps {
         self.clone() * self
     }
 
+    fn muladd(&self, b: &Self, c: &Self) -> Self {
+        self.clone() * b + c
+    }
+
     fn invert(&self) -> Option<Self> {
         FieldOps::invert(self)
     }
@@ -74,12 +78,7 @@ pub trait LoadedScalar<F: PrimeField>: Clone + Debug + PartialEq + FieldOps {
     }
 
     fn powers(&self, n: usize) -> Vec<Self> {
-        iter::once(self.loader().load_one())
-            .chain(
-                iter::successors(Some(self.clone()), |power| Some(power.clone() * self))
-                    .take(n - 1),
-            )
-            .collect_vec()
+        self.loader().powers(self, n)
     }
 }
 
@@ -102,6 +101,145 @@ pub trait EcPointLoader<C: CurveAffine> {
         lhs: &Self::LoadedEcPoint,
         rhs: &Self::LoadedEcPoint,
     ) -> Result<(), Error>;
+
+    /// Constrains `lhs[i]` and `rhs[i]` to be equal for every `i`, failing
+    /// when lengths differ or naming the first mismatched index.
+    fn assert_eq_many(
+        &self,
+        annotation: &str,
+        lhs: &[Self::LoadedEcPoint],
+        rhs: &[Self::LoadedEcPoint],
+    ) -> Result<(), Error> {
+        if lhs.len() != rhs.len() {
+            return Err(Error::InvalidInput {
+                op: "assert_eq_many",
+                detail: format!(
+                    "Length mismatch {} != {} of {}",
+                    lhs.len(),
+                    rhs.len(),
+                    annotation
+                ),
+            });
+        }
+        for (idx, (lhs, rhs)) in lhs.iter().zip(rhs).enumerate() {
+            self.ec_point_assert_eq(annotation, lhs, rhs).map_err(|_| {
+                Error::AssertionFailureDetailed {
+                    annotation: annotation.to_string(),
+                    detail: format!("Mismatch at index {}", idx),
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Returns a boolean that is one when `lhs` equals `rhs` and zero
+    /// otherwise, without constraining them to be equal.
+    fn points_equal(
+        &self,
+        lhs: &Self::LoadedEcPoint,
+        rhs: &Self::LoadedEcPoint,
+    ) -> <<Self::LoadedEcPoint as LoadedEcPoint<C>>::Loader as ScalarLoader<C::Scalar>>::LoadedScalar;
+
+    /// Returns `sum(coeff * ec_point)` of `terms`, which is the identity
+    /// when there are none.
+    fn linear_combination(
+        &self,
+        terms: &[(C::Scalar, &Self::LoadedEcPoint)],
+    ) -> Result<Self::LoadedEcPoint, Error> {
+        let pairs = terms
+            .iter()
+            .filter(|(coeff, _)| *coeff != C::Scalar::zero())
+            .map(|&(coeff, ec_point)| (ec_point.loader().load_const(&coeff), ec_point.clone()))
+            .collect_vec();
+        if pairs.is_empty() {
+            return Ok(self.ec_point_load_zero());
+        }
+
+        Ok(Self::LoadedEcPoint::multi_scalar_multiplication(pairs))
+    }
+
+    /// Folds `points` as `acc = acc * r + point` in order, computed as a
+    /// single `msm` with coefficients `[r^{n-1}, ..., r, 1]`.
+    fn random_linear_combine(
+        &self,
+        points: &[Self::LoadedEcPoint],
+        r: &<<Self::LoadedEcPoint as LoadedEcPoint<C>>::Loader as ScalarLoader<C::Scalar>>::LoadedScalar,
+    ) -> Self::LoadedEcPoint {
+        if points.is_empty() {
+            return self.ec_point_load_zero();
+        }
+
+        let powers = r.loader().powers(r, points.len());
+        Self::LoadedEcPoint::multi_scalar_multiplication(
+            powers.into_iter().rev().zip(points.iter().cloned()),
+        )
+    }
+
+    /// Combines `(lhs, rhs)` pairing inputs of several KZG accumulators into
+    /// one, folding each side with [`EcPointLoader::random_linear_combine`]
+    /// by the same `r` so a single pairing check covers them all.
+    fn combine_accumulators(
+        &self,
+        accumulators: &[(Self::LoadedEcPoint, Self::LoadedEcPoint)],
+        r: &<<Self::LoadedEcPoint as LoadedEcPoint<C>>::Loader as ScalarLoader<C::Scalar>>::LoadedScalar,
+    ) -> (Self::LoadedEcPoint, Self::LoadedEcPoint) {
+        let (lhs, rhs): (Vec<_>, Vec<_>) = accumulators.iter().cloned().unzip();
+        (
+            self.random_linear_combine(&lhs, r),
+            self.random_linear_combine(&rhs, r),
+        )
+    }
+
+    /// Folds `generators` with the IPA round `challenges` into the final
+    /// commitment key, as a single `msm` where `generators[i]` is scaled by
+    /// `challenges[j]` for every bit `k - 1 - j` set in `i` and by its
+    /// inverse otherwise. Coefficients start from the inverted product of
+    /// all challenges and are built by doubling with the squared challenges,
+    /// so there is one inversion and one multiplication per coefficient.
+    fn ipa_fold(
+        &self,
+        generators: &[Self::LoadedEcPoint],
+        challenges: &[<<Self::LoadedEcPoint as LoadedEcPoint<C>>::Loader as ScalarLoader<
+            C::Scalar,
+        >>::LoadedScalar],
+    ) -> Result<Self::LoadedEcPoint, Error> {
+        if generators.len() != 1 << challenges.len() {
+            return Err(Error::AssertionFailureDetailed {
+                annotation: "ipa_fold".to_string(),
+                detail: format!(
+                    "Expected {} generators for {} challenges but got {}",
+                    1 << challenges.len(),
+                    challenges.len(),
+                    generators.len()
+                ),
+            });
+        }
+
+        let loader = match challenges.first() {
+            Some(challenge) => challenge.loader(),
+            None => return Ok(generators[0].clone()),
+        };
+        let product = challenges
+            .iter()
+            .skip(1)
+            .fold(challenges[0].clone(), |acc, challenge| acc * challenge);
+        let product_inv = LoadedScalar::invert(&product)
+            .ok_or_else(|| Error::AssertionFailure("ipa_fold zero challenge".to_string()))?;
+        loader.assert_one("ipa_fold zero challenge", &(product * &product_inv))?;
+
+        let coeffs = challenges.iter().map(LoadedScalar::square).fold(
+            vec![product_inv],
+            |coeffs, challenge_square| {
+                coeffs
+                    .into_iter()
+                    .flat_map(|coeff| [coeff.clone(), coeff * &challenge_square])
+                    .collect()
+            },
+        );
+        Ok(Self::LoadedEcPoint::multi_scalar_multiplication(
+            coeffs.into_iter().zip(generators.iter().cloned()),
+        ))
+    }
 }
 
 pub trait ScalarLoader<F: PrimeField> {
@@ -124,6 +262,85 @@ pub trait ScalarLoader<F: PrimeField> {
         rhs: &Self::LoadedScalar,
     ) -> Result<(), Error>;
 
+    fn assert_zero(&self, annotation: &str, x: &Self::LoadedScalar) -> Result<(), Error> {
+        self.assert_eq(annotation, x, &self.load_zero())
+    }
+
+    fn assert_one(&self, annotation: &str, x: &Self::LoadedScalar) -> Result<(), Error> {
+        self.assert_eq(annotation, x, &self.load_one())
+    }
+
+    /// Constrains `x` to be non-zero by witnessing its inverse.
+    fn assert_non_zero(&self, annotation: &str, x: &Self::LoadedScalar) -> Result<(), Error> {
+        let x_inv = LoadedScalar::invert(x)
+            .ok_or_else(|| Error::AssertionFailure(annotation.to_string()))?;
+        self.assert_one(annotation, &(x.clone() * &x_inv))
+    }
+
+    /// Constrains `lhs` and `rhs` to be equal only when `cond` is one, by
+    /// asserting `cond * (lhs - rhs) == 0`.
+    fn conditional_assert_eq(
+        &self,
+        annotation: &str,
+        cond: &Self::LoadedScalar,
+        lhs: &Self::LoadedScalar,
+        rhs: &Self::LoadedScalar,
+    ) -> Result<(), Error> {
+        let diff = self.sum_products_with_coeff_and_const(
+            &[(F::one(), cond, lhs), (-F::one(), cond, rhs)],
+            F::zero(),
+        );
+        self.assert_eq(annotation, &diff, &self.load_zero())
+    }
+
+    /// Returns `-x` when `bit` is one and `x` when it's zero, by computing
+    /// `x * (1 - 2 * bit)` with `bit` constrained to be boolean, which fails
+    /// when it's known not to be.
+    fn conditional_negate(
+        &self,
+        x: &Self::LoadedScalar,
+        bit: &Self::LoadedScalar,
+    ) -> Result<Self::LoadedScalar, Error> {
+        self.assert_eq("bit is boolean", &bit.square(), bit)?;
+        let one = self.load_one();
+        Ok(self.sum_products_with_coeff_and_const(
+            &[(F::one(), x, &one), (-F::from(2), bit, x)],
+            F::zero(),
+        ))
+    }
+
+    /// Returns a boolean that is one when `lhs` equals `rhs` and zero
+    /// otherwise, without constraining them to be equal.
+    fn is_equal(&self, lhs: &Self::LoadedScalar, rhs: &Self::LoadedScalar) -> Self::LoadedScalar;
+
+    /// Returns a boolean that is one when `x` equals any constant in `set`
+    /// and zero otherwise, by checking whether `prod_i (x - set_i)` vanishes.
+    fn is_member_of(&self, x: &Self::LoadedScalar, set: &[F]) -> Self::LoadedScalar {
+        if set.is_empty() {
+            return self.load_zero();
+        }
+        let diffs = set
+            .iter()
+            .map(|s_i| self.sum_with_const(&[x], -*s_i))
+            .collect_vec();
+        let vanishing = self.product(&diffs.iter().collect_vec());
+        self.is_equal(&vanishing, &self.load_zero())
+    }
+
+    /// Returns the little-endian bits of `x`, constrained to be boolean and to
+    /// compose back to `x`, which therefore must fit in `num_bits`. It fails
+    /// when `x` is known not to.
+    fn to_bits(
+        &self,
+        x: &Self::LoadedScalar,
+        num_bits: usize,
+    ) -> Result<Vec<Self::LoadedScalar>, Error>;
+
+    /// Composes byte-valued `bytes` in little-endian into a scalar, which
+    /// constrains each byte to be in `[0, 256)` and the composition to be
+    /// reduced.
+    fn from_bytes_le(&self, bytes: &[Self::LoadedScalar]) -> Self::LoadedScalar;
+
     fn sum_with_coeff_and_const(
         &self,
         values: &[(F, &Self::LoadedScalar)],
@@ -178,6 +395,70 @@ pub trait ScalarLoader<F: PrimeField> {
             .unwrap()
     }
 
+    /// Returns `x^{2^k}` in exactly `k` squarings, which is `x` itself for
+    /// `k == 0`.
+    fn repeated_square(&self, x: &Self::LoadedScalar, k: usize) -> Self::LoadedScalar {
+        iter::successors(Some(x.clone()), |x| Some(x.square()))
+            .nth(k)
+            .unwrap()
+    }
+
+    /// Returns `x^5`, the Poseidon S-box, as `(x^2)^2 * x` in three
+    /// multiplications.
+    fn pow5(&self, x: &Self::LoadedScalar) -> Self::LoadedScalar {
+        x.square().square() * x
+    }
+
+    /// Returns `L_0(x)` of `domain`, for `x` outside the domain.
+    fn lagrange_first(&self, domain: &Domain<F>, x: &Self::LoadedScalar) -> Self::LoadedScalar {
+        self.lagrange_at_rotation(domain, x, Rotation::cur())
+    }
+
+    /// Returns `L_last(x)` of `domain`, where the last usable row is the one
+    /// right before the `num_blinding_factors` blinded rows, for `x` outside
+    /// the domain.
+    fn lagrange_last(
+        &self,
+        domain: &Domain<F>,
+        x: &Self::LoadedScalar,
+        num_blinding_factors: usize,
+    ) -> Self::LoadedScalar {
+        self.lagrange_at_rotation(domain, x, Rotation(-(num_blinding_factors as i32 + 1)))
+    }
+
+    /// Returns `L_i(x) = omega^i * (x^n - 1) / (n * (x - omega^i))` of
+    /// `domain` for `i` given as `rotation`, for `x` outside the domain.
+    fn lagrange_at_rotation(
+        &self,
+        domain: &Domain<F>,
+        x: &Self::LoadedScalar,
+        rotation: Rotation,
+    ) -> Self::LoadedScalar {
+        let omega_i = domain.rotate_scalar(F::one(), rotation);
+        let coeff = omega_i * domain.n_inv;
+        let xn = x.pow_const(domain.n as u64);
+        let numer = self.sum_with_coeff_and_const(&[(coeff, &xn)], -coeff);
+        let denom = self.sum_with_const(&[x], -omega_i);
+        numer * &LoadedScalar::invert(&denom).unwrap()
+    }
+
+    /// Returns `c * x` for every `x` in `xs`, which are zero or `xs` itself
+    /// when `c` is zero or one.
+    fn scale(&self, c: F, xs: &[Self::LoadedScalar]) -> Vec<Self::LoadedScalar> {
+        if c == F::zero() {
+            return xs.iter().map(|_| self.load_zero()).collect();
+        }
+        if c == F::one() {
+            return xs.to_vec();
+        }
+        xs.iter().map(|x| self.sum_with_coeff(&[(c, x)])).collect()
+    }
+
+    /// Returns `a * x + b` in a single `sum_with_coeff_and_const`.
+    fn affine(&self, a: F, x: &Self::LoadedScalar, b: F) -> Self::LoadedScalar {
+        self.sum_with_coeff_and_const(&[(a, x)], b)
+    }
+
     fn sum_with_coeff(&self, values: &[(F, &Self::LoadedScalar)]) -> Self::LoadedScalar {
         self.sum_with_coeff_and_const(values, F::zero())
     }
@@ -221,6 +502,98 @@ pub trait ScalarLoader<F: PrimeField> {
         self.sum_products_with_const(values, F::zero())
     }
 
+    /// Returns `[1, x, x^2, ..., x^{n-1}]`, where the first element is always
+    /// the loaded constant one.
+    fn powers(&self, x: &Self::LoadedScalar, n: usize) -> Vec<Self::LoadedScalar> {
+        iter::successors(Some(self.load_one()), |power| Some(power.clone() * x))
+            .take(n)
+            .collect_vec()
+    }
+
+    /// Evaluates each of `coeff_sets`, ordered from the constant term up, at
+    /// `x`, sharing a single vector of powers of `x` across all of them.
+    fn batch_horner(
+        &self,
+        coeff_sets: &[&[Self::LoadedScalar]],
+        x: &Self::LoadedScalar,
+    ) -> Vec<Self::LoadedScalar> {
+        let max_len = coeff_sets.iter().map(|coeffs| coeffs.len()).max();
+        let powers = self.powers(x, max_len.unwrap_or_default());
+        coeff_sets
+            .iter()
+            .map(|coeffs| self.sum_products(&coeffs.iter().zip(powers.iter()).collect_vec()))
+            .collect()
+    }
+
+    /// Evaluates at `x` the polynomial of the lowest degree passing through
+    /// `points` given as `(x_i, y_i)`. The barycentric weights take `O(n^2)`
+    /// multiplications and a single batch inversion, while the numerators
+    /// take `O(n)` through prefix and suffix products. Repeated `x_i` are
+    /// rejected by value, or else by asserting the product of the weights'
+    /// denominators, which is nonzero only when all `x_i` are distinct, to be
+    /// invertible.
+    fn lagrange_interpolate(
+        &self,
+        points: &[(Self::LoadedScalar, Self::LoadedScalar)],
+        x: &Self::LoadedScalar,
+    ) -> Result<Self::LoadedScalar, Error> {
+        for (i, (x_i, _)) in points.iter().enumerate() {
+            if points[..i].iter().any(|(x_j, _)| x_j == x_i) {
+                return Err(Error::AssertionFailure(format!(
+                    "Duplicate x-coordinate at index {}",
+                    i
+                )));
+            }
+        }
+
+        let mut weights = points
+            .iter()
+            .enumerate()
+            .map(|(i, (x_i, _))| {
+                let diffs = points
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, (x_j, _))| x_i.clone() - x_j)
+                    .collect_vec();
+                self.product(&diffs.iter().collect_vec())
+            })
+            .collect_vec();
+        // Up to sign, the square of the product of all pairwise differences
+        let distinct = self.product(&weights.iter().collect_vec());
+        let mut distinct_inv = distinct.clone();
+        Self::LoadedScalar::batch_invert(weights.iter_mut().chain(iter::once(&mut distinct_inv)));
+        self.assert_eq(
+            "Duplicate x-coordinates",
+            &(distinct * &distinct_inv),
+            &self.load_one(),
+        )?;
+
+        let diffs = points.iter().map(|(x_j, _)| x.clone() - x_j).collect_vec();
+        let mut prefixes = Vec::with_capacity(diffs.len());
+        let mut suffixes = Vec::with_capacity(diffs.len());
+        let (mut prefix, mut suffix) = (self.load_one(), self.load_one());
+        for i in 0..diffs.len() {
+            prefixes.push(prefix.clone());
+            suffixes.push(suffix.clone());
+            if i + 1 < diffs.len() {
+                prefix = prefix * &diffs[i];
+                suffix = suffix * &diffs[diffs.len() - 1 - i];
+            }
+        }
+        suffixes.reverse();
+
+        let terms = points
+            .iter()
+            .zip(weights)
+            .zip(prefixes.iter().zip(suffixes.iter()))
+            .map(|(((_, y_i), weight), (prefix, suffix))| {
+                self.product(&[prefix, suffix, y_i, &weight])
+            })
+            .collect_vec();
+        Ok(self.sum(&terms.iter().collect_vec()))
+    }
+
     fn product(&self, values: &[&Self::LoadedScalar]) -> Self::LoadedScalar {
         values
             .iter()
@@ -352,6 +352,43 @@ impl EvmLoader {
         self.scalar(Value::Memory(ptr))
     }
 
+    fn push_ec_point_coordinate(self: &Rc<Self>, ec_point: &EcPoint, offset: usize) {
+        match ec_point.value {
+            Value::Constant((x, y)) => {
+                self.code.borrow_mut().push(if offset == 0 { x } else { y });
+            }
+            Value::Memory(ptr) => {
+                self.code.borrow_mut().push(ptr + offset).mload();
+            }
+            Value::Negated(_) | Value::Sum(_, _) | Value::Product(_, _) => {
+                unreachable!()
+            }
+        }
+    }
+
+    fn ec_point_is_equal(self: &Rc<Self>, lhs: &EcPoint, rhs: &EcPoint) -> Scalar {
+        if let (Value::Constant(lhs), Value::Constant(rhs)) = (&lhs.value, &rhs.value) {
+            return self.scalar(Value::Constant(U256::from((lhs == rhs) as u64)));
+        }
+
+        for offset in [0, 0x20] {
+            self.push_ec_point_coordinate(lhs, offset);
+            self.push_ec_point_coordinate(rhs, offset);
+            // [..., lhs_coordinate, rhs_coordinate]
+            self.code.borrow_mut().eq();
+            // [..., coordinate_is_equal]
+        }
+        let ptr = self.allocate(0x20);
+        self.code
+            .borrow_mut()
+            // [..., x_is_equal, y_is_equal]
+            .and()
+            // [..., is_equal]
+            .push(ptr)
+            .mstore();
+        self.scalar(Value::Memory(ptr))
+    }
+
     pub fn dup_ec_point(self: &Rc<Self>, value: &EcPoint) -> EcPoint {
         let ptr = self.allocate(0x40);
         match value.value {
@@ -839,6 +876,10 @@ where
     fn ec_point_assert_eq(&self, _: &str, _: &EcPoint, _: &EcPoint) -> Result<(), Error> {
         unimplemented!()
     }
+
+    fn points_equal(&self, lhs: &EcPoint, rhs: &EcPoint) -> Scalar {
+        self.ec_point_is_equal(lhs, rhs)
+    }
 }
 
 impl<F: PrimeField<Repr = [u8; 0x20]>> ScalarLoader<F> for Rc<EvmLoader> {
@@ -852,6 +893,133 @@ impl<F: PrimeField<Repr = [u8; 0x20]>> ScalarLoader<F> for Rc<EvmLoader> {
         unimplemented!()
     }
 
+    fn is_equal(&self, lhs: &Scalar, rhs: &Scalar) -> Scalar {
+        if let (Value::Constant(lhs), Value::Constant(rhs)) = (&lhs.value, &rhs.value) {
+            return self.scalar(Value::Constant(U256::from((lhs == rhs) as u64)));
+        }
+
+        let diff = EvmLoader::sub(self, lhs, rhs);
+        self.push(&diff);
+        let ptr = self.allocate(0x20);
+        self.code.borrow_mut().iszero().push(ptr).mstore();
+        self.scalar(Value::Memory(ptr))
+    }
+
+    fn to_bits(&self, x: &Scalar, num_bits: usize) -> Result<Vec<Scalar>, Error> {
+        if let Value::Constant(constant) = x.value {
+            if constant.bits() > num_bits {
+                return Err(Error::AssertionFailure(format!(
+                    "Value doesn't fit in {} bits",
+                    num_bits
+                )));
+            }
+            return Ok((0..num_bits)
+                .map(|idx| self.scalar(Value::Constant(U256::from(constant.bit(idx) as u64))))
+                .collect());
+        }
+
+        if num_bits < 256 {
+            self.push(x);
+            self.code
+                .borrow_mut()
+                // [..., success, x]
+                .push(num_bits)
+                // [..., success, x, num_bits]
+                .shr()
+                // [..., success, x >> num_bits]
+                .iszero()
+                // [..., success, x_fits]
+                .and();
+            // [..., success]
+        }
+        Ok((0..num_bits)
+            .map(|idx| {
+                self.push(x);
+                let ptr = self.allocate(0x20);
+                self.code
+                    .borrow_mut()
+                    // [..., x]
+                    .push(idx)
+                    // [..., x, idx]
+                    .shr()
+                    // [..., x >> idx]
+                    .push(1)
+                    // [..., x >> idx, 1]
+                    .and()
+                    // [..., bit]
+                    .push(ptr)
+                    .mstore();
+                self.scalar(Value::Memory(ptr))
+            })
+            .collect())
+    }
+
+    fn from_bytes_le(&self, bytes: &[Scalar]) -> Scalar {
+        assert!(bytes.len() <= 0x20);
+
+        if let Some(bytes) = bytes
+            .iter()
+            .map(|byte| match byte.value {
+                Value::Constant(constant) => Some(constant),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+        {
+            let composed = bytes.iter().rev().fold(U256::zero(), |acc, byte| {
+                assert!(*byte < U256::from(256));
+                (acc << 8) | *byte
+            });
+            assert!(composed < self.scalar_modulus);
+            return self.scalar(Value::Constant(composed));
+        }
+
+        for byte in bytes {
+            self.push(byte);
+            self.code
+                .borrow_mut()
+                // [..., success, byte]
+                .push(8)
+                // [..., success, byte, 8]
+                .shr()
+                // [..., success, byte >> 8]
+                .iszero()
+                // [..., success, byte_fits]
+                .and();
+            // [..., success]
+        }
+        for (idx, byte) in bytes.iter().enumerate() {
+            self.push(byte);
+            // [..., success, acc, byte_i]
+            if idx > 0 {
+                self.code
+                    .borrow_mut()
+                    .push(idx * 8)
+                    // [..., success, acc, byte_i, shift]
+                    .shl()
+                    // [..., success, acc, byte_i << shift]
+                    .or();
+                // [..., success, acc]
+            }
+        }
+        let ptr = self.allocate(0x20);
+        self.code
+            .borrow_mut()
+            // [..., success, composed]
+            .dup(0)
+            // [..., success, composed, composed]
+            .push(ptr)
+            // [..., success, composed, composed, ptr]
+            .mstore()
+            // [..., success, composed]
+            .push(self.scalar_modulus)
+            // [..., success, composed, r]
+            .gt()
+            // [..., success, composed_lt_r]
+            .and();
+        // [..., success]
+        self.scalar(Value::Memory(ptr))
+    }
+
     fn sum_with_coeff_and_const(&self, values: &[(F, &Scalar)], constant: F) -> Scalar {
         if values.is_empty() {
             return self.load_const(&constant);
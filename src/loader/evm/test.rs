@@ -54,3 +54,106 @@ pub fn execute(code: Vec<u8>, calldata: Vec<u8>) -> (bool, u64, Vec<u64>) {
 
     (!result.reverted, result.gas_used, costs)
 }
+
+fn calldata_of_ec_points(ec_points: &[halo2_curves::bn256::G1Affine]) -> Vec<u8> {
+    use crate::{loader::evm::fe_to_u256, util::arithmetic::CurveAffine};
+
+    ec_points
+        .iter()
+        .flat_map(|ec_point| {
+            let coordinates = ec_point.coordinates().unwrap();
+            [*coordinates.x(), *coordinates.y()]
+        })
+        .flat_map(|coordinate| {
+            let mut bytes = [0; 0x20];
+            fe_to_u256(coordinate).to_big_endian(&mut bytes);
+            bytes
+        })
+        .collect()
+}
+
+#[test]
+fn test_points_equal() {
+    use crate::{
+        loader::{evm::EvmLoader, EcPointLoader},
+        util::arithmetic::{Curve, PrimeCurveAffine},
+    };
+    use halo2_curves::bn256::{Fq, Fr, G1Affine};
+
+    let g = G1Affine::generator();
+    let g_double = (g + g).to_affine();
+    for (lhs, rhs, expected) in [(g, g, true), (g, g_double, false)] {
+        let loader = EvmLoader::new::<Fq, Fr>();
+        let [lhs_loaded, rhs_loaded] = [0, 0x40].map(|offset| loader.calldataload_ec_point(offset));
+        let is_equal = EcPointLoader::<G1Affine>::points_equal(&loader, &lhs_loaded, &rhs_loaded);
+        // Fold into the success flag so the execution reverts when unequal
+        loader.code_mut().push(is_equal.ptr()).mload().and();
+
+        let (success, _, _) = execute(loader.runtime_code(), calldata_of_ec_points(&[lhs, rhs]));
+        assert_eq!(success, expected);
+    }
+}
+
+#[test]
+fn test_to_bits() {
+    use crate::loader::{
+        evm::{EvmLoader, U256},
+        ScalarLoader,
+    };
+    use halo2_curves::bn256::{Fq, Fr};
+
+    for (value, num_bits, expected) in [(0b1011u64, 4, true), (0b10110, 4, false)] {
+        let loader = EvmLoader::new::<Fq, Fr>();
+        let x = loader.calldataload_scalar(0);
+        let bits = ScalarLoader::<Fr>::to_bits(&loader, &x, num_bits).unwrap();
+        // Fold each bit's equality to the expected one into the success flag
+        for (idx, bit) in bits.iter().enumerate() {
+            loader
+                .code_mut()
+                .push((value >> idx) & 1)
+                .push(bit.ptr())
+                .mload()
+                .eq()
+                .and();
+        }
+
+        let mut calldata = [0; 0x20];
+        U256::from(value).to_big_endian(&mut calldata);
+        let (success, _, _) = execute(loader.runtime_code(), calldata.to_vec());
+        assert_eq!(success, expected);
+    }
+}
+
+#[test]
+fn test_from_bytes_le() {
+    use crate::loader::{
+        evm::{EvmLoader, U256},
+        ScalarLoader,
+    };
+    use halo2_curves::bn256::{Fq, Fr};
+
+    for (bytes, expected) in [([0x34, 0x12], true), ([0x1234, 0], false)] {
+        let loader = EvmLoader::new::<Fq, Fr>();
+        let loaded = [0, 0x20].map(|offset| loader.calldataload_scalar(offset));
+        let composed = ScalarLoader::<Fr>::from_bytes_le(&loader, &loaded);
+        // Fold the composition's equality to 0x1234 into the success flag
+        loader
+            .code_mut()
+            .push(0x1234)
+            .push(composed.ptr())
+            .mload()
+            .eq()
+            .and();
+
+        let calldata = bytes
+            .into_iter()
+            .flat_map(|byte: u64| {
+                let mut bytes = [0; 0x20];
+                U256::from(byte).to_big_endian(&mut bytes);
+                bytes
+            })
+            .collect();
+        let (success, _, _) = execute(loader.runtime_code(), calldata);
+        assert_eq!(success, expected);
+    }
+}
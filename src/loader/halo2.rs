@@ -1,11 +1,17 @@
 pub(crate) mod loader;
 mod shim;
 
+#[cfg(feature = "op_trace")]
+pub mod trace;
+
 #[cfg(test)]
 pub(crate) mod test;
 
-pub use loader::{EcPoint, Halo2Loader, Scalar};
-pub use shim::{Context, EccInstructions, IntegerInstructions};
+pub use loader::{
+    CapacityHint, ConstantRegistry, DefaultMsmStrategy, EcPoint, Halo2Loader, LazyScalar,
+    MsmStrategy, ReprForm, Scalar, ScalarAccumulator, Snapshot, ToNative,
+};
+pub use shim::{Context, EccInstructions, IntegerInstructions, LimbInfo, PackingHint};
 pub use util::Valuetools;
 
 pub use halo2_wrong_ecc;
@@ -1,21 +1,34 @@
+#[cfg(feature = "op_trace")]
+use crate::loader::halo2::trace::{Op, OpTrace};
 use crate::{
     loader::{
-        halo2::shim::{EccInstructions, IntegerInstructions},
+        halo2::shim::{Context, EccInstructions, IntegerInstructions, LimbInfo, PackingHint},
+        native::NativeLoader,
         EcPointLoader, LoadedEcPoint, LoadedScalar, Loader, ScalarLoader,
     },
     util::{
-        arithmetic::{CurveAffine, Field, FieldOps},
+        arithmetic::{
+            fe_from_big, fe_to_big, modulus, Coordinates, CurveAffine, Field, FieldOps, PrimeField,
+        },
+        hash::Poseidon,
+        transcript::Transcript,
         Itertools,
     },
 };
-use halo2_proofs::circuit;
+use halo2_proofs::{circuit, plonk};
+use num_bigint::BigUint;
+#[cfg(test)]
+use std::borrow::Cow;
+#[cfg(debug_assertions)]
+use std::collections::BTreeSet;
 use std::{
-    cell::{Ref, RefCell, RefMut},
+    cell::{Cell, Ref, RefCell, RefMut},
     collections::btree_map::{BTreeMap, Entry},
     fmt::{self, Debug},
+    hash::{Hash, Hasher},
     iter,
     marker::PhantomData,
-    ops::{Add, AddAssign, Deref, Mul, MulAssign, Neg, Sub, SubAssign},
+    ops::{Add, AddAssign, Deref, Div, Mul, MulAssign, Neg, Sub, SubAssign},
     rc::Rc,
 };
 
@@ -25,26 +38,477 @@ pub struct Halo2Loader<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> {
     ctx: RefCell<EccChip::Context>,
     num_scalar: RefCell<usize>,
     num_ec_point: RefCell<usize>,
+    last_offset: Cell<usize>,
+    scalar_regions: RefCell<Vec<(usize, usize)>>,
+    ec_point_regions: RefCell<Vec<(usize, usize)>>,
     const_ec_point: RefCell<BTreeMap<(C::Base, C::Base), EcPoint<'a, C, EccChip>>>,
+    const_ec_point_coordinates: RefCell<BTreeMap<usize, (C::Base, C::Base)>>,
+    complete_addition: Cell<bool>,
+    max_msm_terms: Cell<Option<usize>>,
+    max_depth: Cell<Option<usize>>,
+    depth: Cell<usize>,
+    msm_strategy: RefCell<Rc<dyn MsmStrategy<'a, C, EccChip> + 'a>>,
+    last_msm_arity: Cell<Option<usize>>,
+    msm_histogram: RefCell<BTreeMap<usize, (usize, usize)>>,
+    public_outputs: RefCell<Vec<(usize, usize, EccChip::AssignedScalar)>>,
+    breadcrumbs: RefCell<Option<Vec<String>>>,
+    #[cfg(debug_assertions)]
+    unconstrained: RefCell<(BTreeSet<usize>, BTreeSet<usize>)>,
+    #[cfg(feature = "op_trace")]
+    op_trace: RefCell<OpTrace<C>>,
+    #[cfg(feature = "symbol_table")]
+    symbols: RefCell<BTreeMap<String, (usize, Value<C::Scalar, EccChip::AssignedScalar>)>>,
     _marker: PhantomData<C>,
     #[cfg(test)]
-    row_meterings: RefCell<Vec<(String, usize)>>,
+    row_meterings: RefCell<Vec<(Cow<'static, str>, usize)>>,
+    #[cfg(test)]
+    row_metering_starts: RefCell<Vec<(usize, usize)>>,
+}
+
+/// Expected sizes of the loader's internal bookkeeping, used to reserve
+/// allocations up front when synthesizing large verifiers. Cached constant
+/// points are kept in an ordered map, which has nothing to reserve.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CapacityHint {
+    /// Number of scalars loaded, constants included.
+    pub num_scalars: usize,
+    /// Number of points loaded, constants included.
+    pub num_ec_points: usize,
+    pub num_meterings: usize,
+}
+
+/// Algorithm computing `sum(scalar * ec_point)` for
+/// [`Halo2Loader::try_multi_scalar_multiplication`], which is given the
+/// non-identity terms in order of point and then scalar index.
+pub trait MsmStrategy<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>>: Debug {
+    fn msm(
+        &self,
+        loader: &Rc<Halo2Loader<'a, C, EccChip>>,
+        pairs: Vec<(Scalar<'a, C, EccChip>, EcPoint<'a, C, EccChip>)>,
+    ) -> Result<EcPoint<'a, C, EccChip>, crate::Error>;
+}
+
+/// Strategy of [`Halo2Loader::split_multi_scalar_multiplication`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultMsmStrategy;
+
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> MsmStrategy<'a, C, EccChip>
+    for DefaultMsmStrategy
+{
+    fn msm(
+        &self,
+        loader: &Rc<Halo2Loader<'a, C, EccChip>>,
+        pairs: Vec<(Scalar<'a, C, EccChip>, EcPoint<'a, C, EccChip>)>,
+    ) -> Result<EcPoint<'a, C, EccChip>, crate::Error> {
+        loader.split_multi_scalar_multiplication(pairs)
+    }
+}
+
+/// Interpretation of the little-endian bytes given to
+/// [`Halo2Loader::load_const_repr`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReprForm {
+    /// Bytes of the value itself.
+    Canonical,
+    /// Bytes of the value times `R = 2^(8 * len)`, where `len` is the length
+    /// of the field's representation.
+    Montgomery,
+}
+
+/// Set of constant points, such as generators, shared across loaders to have
+/// them assigned up front instead of on first use. The identity is never
+/// assigned so it's not kept.
+#[derive(Clone, Debug, Default)]
+pub struct ConstantRegistry<C: CurveAffine> {
+    points: BTreeMap<(C::Base, C::Base), C>,
+}
+
+impl<C: CurveAffine> ConstantRegistry<C> {
+    pub fn new() -> Self {
+        Self {
+            points: BTreeMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, point: C) {
+        if let Some(coordinates) = Option::<Coordinates<C>>::from(point.coordinates()) {
+            self.points
+                .insert((*coordinates.x(), *coordinates.y()), point);
+        }
+    }
+
+    pub fn points(&self) -> Vec<C> {
+        self.points.values().copied().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+impl<C: CurveAffine> FromIterator<C> for ConstantRegistry<C> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        let mut registry = Self::new();
+        for point in iter {
+            registry.insert(point);
+        }
+        registry
+    }
 }
 
 impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, EccChip> {
     pub fn new(ecc_chip: EccChip, ctx: EccChip::Context) -> Rc<Self> {
+        Self::with_capacity(ecc_chip, ctx, CapacityHint::default())
+    }
+
+    /// Same as [`Halo2Loader::new`] but validates `ecc_chip`'s parameters
+    /// first.
+    pub fn try_new(ecc_chip: EccChip, ctx: EccChip::Context) -> Result<Rc<Self>, crate::Error> {
+        ecc_chip
+            .validate_params()
+            .map_err(|source| crate::Error::ChipError {
+                op: "validate_params",
+                source,
+            })?;
+        Ok(Self::new(ecc_chip, ctx))
+    }
+
+    /// Same as [`Halo2Loader::new`] but fails when `ctx` already has rows
+    /// assigned, which is usually a sign of being given the wrong region.
+    pub fn new_at_fresh_region(
+        ecc_chip: EccChip,
+        ctx: EccChip::Context,
+    ) -> Result<Rc<Self>, crate::Error> {
+        Self::new_at_offset(ecc_chip, ctx, 0)
+    }
+
+    /// Same as [`Halo2Loader::new`] but fails when `ctx` is not at
+    /// `expected_offset`.
+    pub fn new_at_offset(
+        ecc_chip: EccChip,
+        ctx: EccChip::Context,
+        expected_offset: usize,
+    ) -> Result<Rc<Self>, crate::Error> {
+        if ctx.offset() != expected_offset {
+            return Err(crate::Error::InvalidInput {
+                op: "new_at_offset",
+                detail: format!(
+                    "Context is at offset {} instead of {}",
+                    ctx.offset(),
+                    expected_offset
+                ),
+            });
+        }
+        Ok(Self::new(ecc_chip, ctx))
+    }
+
+    /// Same as [`Halo2Loader::new`] but with points of `registry` assigned
+    /// up front, so they are served from the cache right away.
+    pub fn with_registry(
+        ecc_chip: EccChip,
+        ctx: EccChip::Context,
+        registry: &ConstantRegistry<C>,
+    ) -> Rc<Self> {
+        let loader = Self::new(ecc_chip, ctx);
+        loader.load_registry(registry);
+        loader
+    }
+
+    /// Same as [`Halo2Loader::new`] but with bookkeeping sized by `hint`, so
+    /// loading up to that many scalars and points doesn't reallocate.
+    pub fn with_capacity(ecc_chip: EccChip, ctx: EccChip::Context, hint: CapacityHint) -> Rc<Self> {
         Rc::new(Self {
             ecc_chip: RefCell::new(ecc_chip),
             ctx: RefCell::new(ctx),
             num_scalar: RefCell::default(),
             num_ec_point: RefCell::default(),
+            last_offset: Cell::default(),
+            scalar_regions: RefCell::new(Vec::with_capacity(hint.num_scalars)),
+            ec_point_regions: RefCell::new(Vec::with_capacity(hint.num_ec_points)),
             const_ec_point: RefCell::default(),
+            const_ec_point_coordinates: RefCell::default(),
+            complete_addition: Cell::new(false),
+            max_msm_terms: Cell::default(),
+            max_depth: Cell::default(),
+            depth: Cell::default(),
+            msm_strategy: RefCell::new(Rc::new(DefaultMsmStrategy)),
+            last_msm_arity: Cell::default(),
+            msm_histogram: RefCell::default(),
+            public_outputs: RefCell::default(),
+            breadcrumbs: RefCell::default(),
+            #[cfg(debug_assertions)]
+            unconstrained: RefCell::default(),
+            #[cfg(feature = "op_trace")]
+            op_trace: RefCell::default(),
+            #[cfg(feature = "symbol_table")]
+            symbols: RefCell::default(),
+            #[cfg(test)]
+            row_meterings: RefCell::new(Vec::with_capacity(hint.num_meterings)),
             #[cfg(test)]
-            row_meterings: RefCell::default(),
+            row_metering_starts: RefCell::default(),
             _marker: PhantomData,
         })
     }
 
+    /// Same as [`Halo2Loader::new`] but computing every `msm` with
+    /// `strategy`.
+    pub fn with_msm_strategy(
+        ecc_chip: EccChip,
+        ctx: EccChip::Context,
+        strategy: Rc<dyn MsmStrategy<'a, C, EccChip> + 'a>,
+    ) -> Rc<Self> {
+        let loader = Self::new(ecc_chip, ctx);
+        loader.set_msm_strategy(strategy);
+        loader
+    }
+
+    pub fn set_msm_strategy(&self, strategy: Rc<dyn MsmStrategy<'a, C, EccChip> + 'a>) {
+        *self.msm_strategy.borrow_mut() = strategy;
+    }
+
+    /// Caps the number of terms per chip `msm` call below what
+    /// [`EccInstructions::max_msm_terms`] allows.
+    pub fn set_max_msm_terms(&self, max_msm_terms: usize) {
+        assert!(max_msm_terms > 0);
+        self.max_msm_terms.set(Some(max_msm_terms));
+    }
+
+    /// Limits how deep [`Halo2Loader::recurse`] calls can nest.
+    pub fn set_max_depth(&self, max_depth: usize) {
+        self.max_depth.set(Some(max_depth));
+    }
+
+    /// Runs `f`, e.g. verification of a proof nested in the one being
+    /// verified, one level deeper, failing with [`crate::Error::RecursionLimit`]
+    /// instead when already at the limit set by
+    /// [`Halo2Loader::set_max_depth`].
+    pub fn recurse<T>(
+        &self,
+        f: impl FnOnce() -> Result<T, crate::Error>,
+    ) -> Result<T, crate::Error> {
+        let depth = self.depth.get();
+        if let Some(max_depth) = self.max_depth.get() {
+            if depth >= max_depth {
+                return Err(crate::Error::RecursionLimit(max_depth));
+            }
+        }
+        self.depth.set(depth + 1);
+        let output = f();
+        self.depth.set(depth);
+        output
+    }
+
+    fn max_msm_terms(&self) -> usize {
+        let max_msm_terms = self.ecc_chip().max_msm_terms();
+        self.max_msm_terms
+            .get()
+            .map_or(max_msm_terms, |cap| cap.min(max_msm_terms))
+    }
+
+    /// Forces every point addition done by the loader, including the ones
+    /// reducing `multi_scalar_multiplication`, to use complete formulas.
+    pub fn set_complete_addition(&self, complete_addition: bool) {
+        self.complete_addition.set(complete_addition);
+    }
+
+    /// Swaps in a fresh `ctx` and clears counters and caches, so the same
+    /// loader can be reused for another synthesis pass. Cached constant
+    /// points are dropped as well since their cells belong to the previous
+    /// context.
+    pub fn reset(&self, ctx: EccChip::Context) {
+        *self.ctx.borrow_mut() = ctx;
+        *self.num_scalar.borrow_mut() = 0;
+        *self.num_ec_point.borrow_mut() = 0;
+        self.last_offset.set(self.ctx().offset());
+        self.scalar_regions.borrow_mut().clear();
+        self.ec_point_regions.borrow_mut().clear();
+        self.const_ec_point.borrow_mut().clear();
+        self.const_ec_point_coordinates.borrow_mut().clear();
+        self.last_msm_arity.set(None);
+        self.msm_histogram.borrow_mut().clear();
+        self.depth.set(0);
+        self.public_outputs.borrow_mut().clear();
+        if let Some(breadcrumbs) = self.breadcrumbs.borrow_mut().as_mut() {
+            breadcrumbs.clear();
+        }
+        #[cfg(debug_assertions)]
+        self.unconstrained.take();
+        #[cfg(feature = "symbol_table")]
+        self.symbols.borrow_mut().clear();
+        #[cfg(test)]
+        self.row_meterings.borrow_mut().clear();
+        #[cfg(test)]
+        self.row_metering_starts.borrow_mut().clear();
+    }
+
+    /// Returns the scalars marked by [`Scalar::expose_public`] with the row
+    /// they are exposed at, to be passed to
+    /// [`IntegerInstructions::expose_public`] once out of the region. The
+    /// scalars only count as constrained from then on.
+    pub fn take_public_outputs(&self) -> Vec<(usize, EccChip::AssignedScalar)> {
+        let outputs = self.public_outputs.take();
+        let indices = outputs.iter().map(|(_, index, _)| *index).collect_vec();
+        self.mark_constrained(&indices, &[]);
+        outputs
+            .into_iter()
+            .map(|(row, _, assigned)| (row, assigned))
+            .collect()
+    }
+
+    /// Returns an error listing the rows of public outputs never taken by
+    /// [`Halo2Loader::take_public_outputs`], or else the indices of scalars
+    /// and points assigned as witnesses that never took part in any
+    /// constraint, which a malicious prover could set freely.
+    #[cfg(debug_assertions)]
+    pub fn assert_all_constrained(&self) -> Result<(), crate::Error> {
+        let public_outputs = self.public_outputs.borrow();
+        if !public_outputs.is_empty() {
+            return Err(crate::Error::AssertionFailure(format!(
+                "Public outputs at rows {:?} are never taken to be exposed",
+                public_outputs.iter().map(|(row, _, _)| row).collect_vec()
+            )));
+        }
+        let unconstrained = self.unconstrained.borrow();
+        let (scalars, ec_points) = &*unconstrained;
+        if scalars.is_empty() && ec_points.is_empty() {
+            return Ok(());
+        }
+        Err(crate::Error::AssertionFailure(format!(
+            "Unconstrained scalars {:?} and ec points {:?}",
+            scalars, ec_points
+        )))
+    }
+
+    #[allow(unused_variables)]
+    fn mark_unconstrained(&self, scalar: Option<usize>, ec_point: Option<usize>) {
+        #[cfg(debug_assertions)]
+        {
+            let mut unconstrained = self.unconstrained.borrow_mut();
+            unconstrained.0.extend(scalar);
+            unconstrained.1.extend(ec_point);
+        }
+    }
+
+    #[allow(unused_variables)]
+    pub(crate) fn mark_constrained(&self, scalars: &[usize], ec_points: &[usize]) {
+        #[cfg(debug_assertions)]
+        {
+            let mut unconstrained = self.unconstrained.borrow_mut();
+            for index in scalars {
+                unconstrained.0.remove(index);
+            }
+            for index in ec_points {
+                unconstrained.1.remove(index);
+            }
+        }
+    }
+
+    /// Runs `f` and marks `scalars` and `ec_points` as constrained only when it
+    /// laid out any row, so short-circuits returning an operand as is, like
+    /// `x + 0` or `x * 1`, don't count as constraining it.
+    fn constrain_if_gated<T>(
+        &self,
+        scalars: &[usize],
+        ec_points: &[usize],
+        f: impl FnOnce() -> T,
+    ) -> T {
+        let offset = self.ctx().offset();
+        let output = f();
+        if self.ctx().offset() != offset {
+            self.mark_constrained(scalars, ec_points);
+        }
+        output
+    }
+
+    /// Starts recording the annotations of passed assertions, which are then
+    /// listed in the error of the first failing one.
+    pub fn enable_breadcrumbs(&self) {
+        self.breadcrumbs.borrow_mut().get_or_insert_with(Vec::new);
+    }
+
+    /// Returns the annotations of assertions passed so far, empty when
+    /// [`Halo2Loader::enable_breadcrumbs`] hasn't been called.
+    pub fn take_breadcrumbs(&self) -> Vec<String> {
+        self.breadcrumbs
+            .borrow_mut()
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    fn breadcrumb(
+        &self,
+        annotation: &str,
+        result: Result<(), crate::Error>,
+    ) -> Result<(), crate::Error> {
+        let mut breadcrumbs = self.breadcrumbs.borrow_mut();
+        let breadcrumbs = match breadcrumbs.as_mut() {
+            Some(breadcrumbs) => breadcrumbs,
+            None => return result,
+        };
+        match result {
+            Ok(()) => {
+                breadcrumbs.push(annotation.to_string());
+                Ok(())
+            }
+            Err(err) => {
+                let trail = format!("after successfully checking: {:?}", breadcrumbs);
+                Err(match err {
+                    crate::Error::AssertionFailure(annotation) => {
+                        crate::Error::AssertionFailureDetailed {
+                            annotation,
+                            detail: trail,
+                        }
+                    }
+                    crate::Error::AssertionFailureDetailed { annotation, detail } => {
+                        crate::Error::AssertionFailureDetailed {
+                            annotation,
+                            detail: format!("{}, {}", detail, trail),
+                        }
+                    }
+                    err => err,
+                })
+            }
+        }
+    }
+
+    /// Returns the operations recorded so far and starts a new trace.
+    #[cfg(feature = "op_trace")]
+    pub fn take_trace(&self) -> OpTrace<C> {
+        self.op_trace.take()
+    }
+
+    #[cfg(feature = "op_trace")]
+    fn record(&self, op: Op<C>) {
+        self.op_trace.borrow_mut().push(op);
+    }
+
+    /// Names `scalar` for later lookup by [`Halo2Loader::get_scalar`],
+    /// replacing any scalar previously bound to `name`.
+    #[cfg(feature = "symbol_table")]
+    pub fn bind(&self, name: &str, scalar: &Scalar<'a, C, EccChip>) {
+        self.symbols
+            .borrow_mut()
+            .insert(name.to_string(), (scalar.index, scalar.value.clone()));
+    }
+
+    /// Returns the scalar bound to `name` by [`Halo2Loader::bind`].
+    #[cfg(feature = "symbol_table")]
+    pub fn get_scalar(self: &Rc<Self>, name: &str) -> Option<Scalar<'a, C, EccChip>> {
+        self.symbols
+            .borrow()
+            .get(name)
+            .map(|(index, value)| Scalar {
+                loader: self.clone(),
+                index: *index,
+                value: value.clone(),
+            })
+    }
+
     pub fn into_ctx(self) -> EccChip::Context {
         self.ctx.into_inner()
     }
@@ -57,6 +521,12 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
         Ref::map(self.ecc_chip(), |ecc_chip| ecc_chip.scalar_chip())
     }
 
+    /// Returns how the scalar chip splits a scalar into limbs, which should
+    /// match the encoding used by the transcript.
+    pub fn scalar_limb_info(&self) -> LimbInfo {
+        self.scalar_chip().limb_info()
+    }
+
     pub fn ctx(&self) -> Ref<'_, EccChip::Context> {
         self.ctx.borrow()
     }
@@ -70,7 +540,107 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
             .scalar_chip()
             .assign_constant(&mut self.ctx_mut(), constant)
             .unwrap();
-        self.scalar(Value::Assigned(assigned))
+        let output = self.scalar(Value::Assigned(assigned));
+        #[cfg(feature = "op_trace")]
+        self.record(Op::AssignConstScalar {
+            output: output.index,
+            value: constant,
+        });
+        output
+    }
+
+    /// Loads `value` as a constant, which costs no rows until it's used where
+    /// a cell is needed. Each such use assigns a new constant cell, so
+    /// literals used repeatedly that way are better assigned once by
+    /// [`Halo2Loader::assign_const_scalar`].
+    pub fn load_u64(&self, value: u64) -> Scalar<'a, C, EccChip> {
+        self.load_const(&C::Scalar::from(value))
+    }
+
+    pub fn load_u128(&self, value: u128) -> Scalar<'a, C, EccChip> {
+        self.load_const(&fe_from_big(BigUint::from(value)))
+    }
+
+    /// Loads a big-endian hex string, with or without `0x` prefix, as a
+    /// constant reduced into the scalar field.
+    pub fn load_const_hex(&self, hex: &str) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let digits = hex.strip_prefix("0x").unwrap_or(hex);
+        let value = BigUint::parse_bytes(digits.as_bytes(), 16)
+            .ok_or_else(|| crate::Error::Parse(format!("Invalid hex string {:?}", hex)))?;
+        Ok(self.load_const(&fe_from_big(value % modulus::<C::Scalar>())))
+    }
+
+    /// Casts `scalar` into the base field, failing when its value is not less
+    /// than the base field modulus.
+    pub fn scalar_to_base(
+        self: &Rc<Self>,
+        scalar: &Scalar<'a, C, EccChip>,
+    ) -> Result<EccChip::AssignedBase, crate::Error> {
+        let mut fits = true;
+        scalar
+            .to_native()
+            .map(|value| fits = fe_to_big(value) < modulus::<C::Base>());
+        if !fits {
+            return Err(crate::Error::ChipError {
+                op: "scalar_to_base",
+                source: "Scalar is not less than the base field modulus".to_string(),
+            });
+        }
+
+        let assigned = scalar.assigned();
+        self.mark_constrained(&[scalar.index], &[]);
+        self.ecc_chip()
+            .scalar_to_base(&mut self.ctx_mut(), &assigned)
+            .map_err(chip_error("scalar_to_base"))
+    }
+
+    /// Returns the Poseidon hash of `inputs` with `r_f` full rounds and `r_p`
+    /// partial rounds, constrained by the same gadget the transcript uses.
+    pub fn hash_to_scalar<const T: usize, const RATE: usize>(
+        self: &Rc<Self>,
+        r_f: usize,
+        r_p: usize,
+        inputs: &[Scalar<'a, C, EccChip>],
+    ) -> Scalar<'a, C, EccChip> {
+        let mut hasher = Poseidon::<C::Scalar, _, T, RATE>::new(self.clone(), r_f, r_p);
+        hasher.update(inputs);
+        hasher.squeeze()
+    }
+
+    /// Loads a constant from little-endian `repr` interpreted as `form`.
+    /// Bytes of a length other than the field's representation or encoding a
+    /// value not less than the modulus are rejected.
+    pub fn load_const_repr(
+        &self,
+        repr: &[u8],
+        form: ReprForm,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let repr_len = <C::Scalar as PrimeField>::Repr::default().as_ref().len();
+        if repr.len() != repr_len {
+            return Err(crate::Error::Parse(format!(
+                "Expected {} bytes but got {}",
+                repr_len,
+                repr.len()
+            )));
+        }
+        let value = BigUint::from_bytes_le(repr);
+        if value >= modulus::<C::Scalar>() {
+            return Err(crate::Error::Parse(format!(
+                "Value {:#x} is not less than the modulus",
+                value
+            )));
+        }
+        let value = fe_from_big::<C::Scalar>(value);
+        let value = match form {
+            ReprForm::Canonical => value,
+            ReprForm::Montgomery => {
+                let r = fe_from_big::<C::Scalar>(
+                    (BigUint::from(1u64) << (8 * repr_len)) % modulus::<C::Scalar>(),
+                );
+                value * r.invert().unwrap()
+            }
+        };
+        Ok(self.load_const(&value))
     }
 
     pub fn assign_scalar(
@@ -81,7 +651,110 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
             .scalar_chip()
             .assign_integer(&mut self.ctx_mut(), scalar)
             .unwrap();
-        self.scalar(Value::Assigned(assigned))
+        let output = self.scalar(Value::Assigned(assigned));
+        self.mark_unconstrained(Some(output.index), None);
+        #[cfg(feature = "op_trace")]
+        self.record(Op::AssignScalar {
+            output: output.index,
+            value: scalar,
+        });
+        output
+    }
+
+    /// Assigns `scalars` as one batch through
+    /// [`IntegerInstructions::assign_integer_packed`], so chips that support
+    /// it can pack them into shared rows.
+    pub fn assign_scalars(
+        self: &Rc<Self>,
+        scalars: impl IntoIterator<Item = circuit::Value<EccChip::Scalar>>,
+    ) -> Vec<Scalar<'a, C, EccChip>> {
+        let scalars = scalars.into_iter().collect_vec();
+        PackingHint::batch(scalars.len())
+            .zip(scalars)
+            .map(|(hint, scalar)| {
+                let assigned = self
+                    .scalar_chip()
+                    .assign_integer_packed(&mut self.ctx_mut(), scalar.clone(), hint)
+                    .unwrap();
+                let output = self.scalar(Value::Assigned(assigned));
+                self.mark_unconstrained(Some(output.index), None);
+                #[cfg(feature = "op_trace")]
+                self.record(Op::AssignScalar {
+                    output: output.index,
+                    value: scalar,
+                });
+                output
+            })
+            .collect()
+    }
+
+    /// Assigns claimed evaluations of committed polynomials, metered under
+    /// `"evaluations"`.
+    pub fn assign_evaluations(
+        self: &Rc<Self>,
+        evals: &[circuit::Value<EccChip::Scalar>],
+    ) -> Vec<Scalar<'a, C, EccChip>> {
+        self.start_cost_metering("evaluations");
+        let evals = self.assign_scalars(evals.iter().cloned());
+        self.end_cost_metering();
+        evals
+    }
+
+    /// Defers assigning `scalar` until [`LazyScalar::scalar`] is first
+    /// called.
+    pub fn assign_scalar_lazily(
+        self: &Rc<Self>,
+        scalar: circuit::Value<EccChip::Scalar>,
+    ) -> LazyScalar<'a, C, EccChip> {
+        LazyScalar {
+            loader: self.clone(),
+            value: scalar,
+            scalar: Rc::default(),
+        }
+    }
+
+    /// Wraps an already assigned cell, e.g. one copied from the instance
+    /// column by `assign_advice_from_instance`, without re-assigning it, so
+    /// the equality constraint to the instance carries over to the returned
+    /// scalar.
+    pub fn scalar_from_instance(
+        self: &Rc<Self>,
+        cell: EccChip::AssignedScalar,
+    ) -> Scalar<'a, C, EccChip> {
+        let output = self.scalar(Value::Assigned(cell));
+        #[cfg(feature = "op_trace")]
+        self.record(Op::AssignScalar {
+            output: output.index,
+            value: output.to_native(),
+        });
+        output
+    }
+
+    /// Composes a scalar from `limbs` assigned by another gadget, least
+    /// significant first, which are checked to be as many and in the ranges
+    /// the scalar chip expects.
+    pub fn scalar_from_limbs(
+        self: &Rc<Self>,
+        limbs: Vec<circuit::AssignedCell<C::Scalar, C::Scalar>>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let num_limbs = self.scalar_limb_info().num_limbs;
+        if limbs.len() != num_limbs {
+            return Err(crate::Error::InvalidInput {
+                op: "scalar_from_limbs",
+                detail: format!("Expected {} limbs but got {}", num_limbs, limbs.len()),
+            });
+        }
+        let assigned = self
+            .scalar_chip()
+            .from_limbs(&mut self.ctx_mut(), limbs)
+            .map_err(chip_error("from_limbs"))?;
+        let output = self.scalar(Value::Assigned(assigned));
+        #[cfg(feature = "op_trace")]
+        self.record(Op::AssignScalar {
+            output: output.index,
+            value: output.to_native(),
+        });
+        Ok(output)
     }
 
     pub(crate) fn scalar(
@@ -90,6 +763,7 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
     ) -> Scalar<'a, C, EccChip> {
         let index = *self.num_scalar.borrow();
         *self.num_scalar.borrow_mut() += 1;
+        self.scalar_regions.borrow_mut().push(self.next_region());
         Scalar {
             loader: self.clone(),
             index,
@@ -97,7 +771,111 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
         }
     }
 
+    /// Returns the range of offsets `[start, end)` assigned while creating
+    /// the scalar of `index`, for mapping failing cells back to operations.
+    pub fn region_of(&self, index: usize) -> Option<(usize, usize)> {
+        self.scalar_regions.borrow().get(index).copied()
+    }
+
+    /// Same as [`Halo2Loader::region_of`] but for the point of `index`.
+    pub fn ec_point_region_of(&self, index: usize) -> Option<(usize, usize)> {
+        self.ec_point_regions.borrow().get(index).copied()
+    }
+
+    fn next_region(&self) -> (usize, usize) {
+        let start = self.last_offset.get();
+        let end = self.ctx().offset();
+        self.last_offset.set(end);
+        (start, end)
+    }
+
+    fn try_assert_eq_many(
+        &self,
+        annotation: &str,
+        lhs: &[EcPoint<'a, C, EccChip>],
+        rhs: &[EcPoint<'a, C, EccChip>],
+    ) -> Result<(), crate::Error> {
+        if lhs.len() != rhs.len() {
+            return Err(crate::Error::InvalidInput {
+                op: "assert_eq_many",
+                detail: format!(
+                    "Length mismatch {} != {} of {}",
+                    lhs.len(),
+                    rhs.len(),
+                    annotation
+                ),
+            });
+        }
+        let mismatch = |idx: usize| crate::Error::AssertionFailureDetailed {
+            annotation: annotation.to_string(),
+            detail: format!("Mismatch at index {}", idx),
+        };
+
+        let mut assigned = Vec::with_capacity(lhs.len());
+        for (idx, (lhs, rhs)) in lhs.iter().zip(rhs).enumerate() {
+            #[cfg(feature = "op_trace")]
+            self.record(Op::EcPointAssertEq {
+                lhs: lhs.index,
+                rhs: rhs.index,
+            });
+            match (lhs.is_identity_const(), rhs.is_identity_const()) {
+                (true, true) => {}
+                (false, false) => {
+                    assigned.push((idx, lhs.assigned()?, rhs.assigned()?));
+                    self.mark_constrained(&[], &[lhs.index, rhs.index]);
+                }
+                _ => return Err(mismatch(idx)),
+            }
+        }
+
+        let ecc_chip = self.ecc_chip();
+        let mut ctx = self.ctx_mut();
+        for (idx, lhs, rhs) in assigned {
+            ecc_chip
+                .assert_equal(&mut ctx, &lhs, &rhs)
+                .map_err(|_| mismatch(idx))?;
+        }
+        Ok(())
+    }
+
+    fn scalar_assertion_failure(
+        &self,
+        annotation: &str,
+        lhs: &EccChip::AssignedScalar,
+        rhs: &EccChip::AssignedScalar,
+    ) -> crate::Error {
+        let scalar_chip = self.scalar_chip();
+        let mut detail = None;
+        for (idx, (lhs, rhs)) in scalar_chip
+            .limbs(lhs)
+            .into_iter()
+            .zip(scalar_chip.limbs(rhs))
+            .enumerate()
+        {
+            lhs.zip(rhs).map(|(lhs, rhs)| {
+                if detail.is_none() && lhs != rhs {
+                    detail = Some(format!(
+                        "limb {} differs, lhs: {:?}, rhs: {:?}",
+                        idx, lhs, rhs
+                    ));
+                }
+            });
+        }
+
+        match detail {
+            Some(detail) => crate::Error::AssertionFailureDetailed {
+                annotation: annotation.to_string(),
+                detail,
+            },
+            None => crate::Error::AssertionFailure(annotation.to_string()),
+        }
+    }
+
     pub fn assign_const_ec_point(self: &Rc<Self>, constant: C) -> EcPoint<'a, C, EccChip> {
+        if bool::from(constant.is_identity()) {
+            return self.ec_point_identity();
+        }
+
         let coordinates = constant.coordinates().unwrap();
         match self
             .const_ec_point
@@ -111,11 +889,127 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
                     .assign_point(&mut self.ctx_mut(), circuit::Value::known(constant))
                     .unwrap();
                 let ec_point = self.ec_point(assigned);
+                self.const_ec_point_coordinates
+                    .borrow_mut()
+                    .insert(ec_point.index, *entry.key());
                 entry.insert(ec_point).clone()
             }
         }
     }
 
+    /// Assigns constant `points` in a batch, where points already in the cache
+    /// or repeated within the batch are only assigned once.
+    pub fn assign_const_ec_points(self: &Rc<Self>, points: &[C]) -> Vec<EcPoint<'a, C, EccChip>> {
+        let keys = points
+            .iter()
+            .map(|point| {
+                Option::<Coordinates<C>>::from(point.coordinates())
+                    .map(|coordinates| (*coordinates.x(), *coordinates.y()))
+            })
+            .collect_vec();
+
+        let novel = {
+            let const_ec_point = self.const_ec_point.borrow();
+            points
+                .iter()
+                .zip(keys.iter())
+                .filter_map(|(point, key)| {
+                    key.filter(|key| !const_ec_point.contains_key(key))
+                        .map(|key| (key, *point))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        if !novel.is_empty() {
+            let assigned = self
+                .ecc_chip()
+                .assign_points(
+                    &mut self.ctx_mut(),
+                    novel.values().copied().map(circuit::Value::known).collect(),
+                )
+                .unwrap();
+            for (key, assigned) in novel.into_keys().zip(assigned) {
+                let ec_point = self.ec_point(assigned);
+                self.const_ec_point_coordinates
+                    .borrow_mut()
+                    .insert(ec_point.index, key);
+                self.const_ec_point.borrow_mut().insert(key, ec_point);
+            }
+        }
+
+        let const_ec_point = self.const_ec_point.borrow();
+        keys.into_iter()
+            .map(|key| match key {
+                Some(key) => const_ec_point[&key].clone(),
+                None => self.ec_point_identity(),
+            })
+            .collect()
+    }
+
+    /// Returns the number of distinct constant points assigned so far.
+    pub fn num_const_ec_points(&self) -> usize {
+        self.const_ec_point.borrow().len()
+    }
+
+    /// Returns the number of points created so far, where constant points
+    /// served from the cache are not counted again.
+    pub fn num_ec_points(&self) -> usize {
+        *self.num_ec_point.borrow()
+    }
+
+    /// Returns the coordinates of constant points cached so far, to be
+    /// preloaded into a later loader with
+    /// [`Halo2Loader::preload_const_points`].
+    pub fn dump_const_points(&self) -> Vec<(C::Base, C::Base)> {
+        self.const_ec_point.borrow().keys().copied().collect()
+    }
+
+    /// Loads the G1 points of an SRS of `len` points as cached constants,
+    /// after checking the length and that every point is on curve.
+    pub fn load_srs(
+        self: &Rc<Self>,
+        srs: &[C],
+        len: usize,
+    ) -> Result<Vec<EcPoint<'a, C, EccChip>>, crate::Error> {
+        if srs.len() != len {
+            return Err(crate::Error::InvalidInput {
+                op: "load_srs",
+                detail: format!("Expected {} points but got {}", len, srs.len()),
+            });
+        }
+        if let Some(idx) = srs
+            .iter()
+            .position(|point| !bool::from(point.is_on_curve()))
+        {
+            return Err(crate::Error::InvalidInput {
+                op: "load_srs",
+                detail: format!("Point {} is not on curve", idx),
+            });
+        }
+        Ok(self.assign_const_ec_points(srs))
+    }
+
+    /// Assigns points of `registry` that are not cached yet.
+    pub fn load_registry(self: &Rc<Self>, registry: &ConstantRegistry<C>) {
+        self.assign_const_ec_points(&registry.points());
+    }
+
+    /// Assigns constant points, such as the ones dumped by
+    /// [`Halo2Loader::dump_const_points`], in a batch, which fails when any of
+    /// them is not on curve.
+    pub fn preload_const_points(self: &Rc<Self>, points: &[C]) -> Result<(), crate::Error> {
+        if let Some(idx) = points
+            .iter()
+            .position(|point| !bool::from(point.is_on_curve()))
+        {
+            return Err(crate::Error::InvalidInput {
+                op: "preload_const_points",
+                detail: format!("Point {} is not on curve", idx),
+            });
+        }
+        self.assign_const_ec_points(points);
+        Ok(())
+    }
+
     pub fn assign_ec_point(
         self: &Rc<Self>,
         ec_point: circuit::Value<C>,
@@ -124,26 +1018,98 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
             .ecc_chip()
             .assign_point(&mut self.ctx_mut(), ec_point)
             .unwrap();
-        self.ec_point(assigned)
+        let output = self.ec_point(assigned);
+        self.mark_unconstrained(None, Some(output.index));
+        #[cfg(feature = "op_trace")]
+        self.record(Op::AssignEcPoint {
+            output: output.index,
+            value: ec_point,
+        });
+        output
+    }
+
+    /// Assigns `ec_points` in one go through [`EccInstructions::assign_points`],
+    /// so chips can batch their on-curve checks.
+    pub fn assign_ec_points(
+        self: &Rc<Self>,
+        ec_points: impl IntoIterator<Item = circuit::Value<C>>,
+    ) -> Vec<EcPoint<'a, C, EccChip>> {
+        let ec_points = ec_points.into_iter().collect_vec();
+        let assigned = self
+            .ecc_chip()
+            .assign_points(&mut self.ctx_mut(), ec_points.clone())
+            .unwrap();
+        assigned
+            .into_iter()
+            .zip(ec_points)
+            .map(|(assigned, _ec_point)| {
+                let output = self.ec_point(assigned);
+                self.mark_unconstrained(None, Some(output.index));
+                #[cfg(feature = "op_trace")]
+                self.record(Op::AssignEcPoint {
+                    output: output.index,
+                    value: _ec_point,
+                });
+                output
+            })
+            .collect()
+    }
+
+    /// Returns the point at infinity, which is tracked by the loader instead
+    /// of being assigned, so it can be skipped by additions and `msm`.
+    pub fn ec_point_identity(self: &Rc<Self>) -> EcPoint<'a, C, EccChip> {
+        self.ec_point_with_normalized(None, true)
     }
 
     fn ec_point(self: &Rc<Self>, assigned: EccChip::AssignedEcPoint) -> EcPoint<'a, C, EccChip> {
+        self.ec_point_with_normalized(Some(assigned), true)
+    }
+
+    fn ec_point_with_normalized(
+        self: &Rc<Self>,
+        assigned: Option<EccChip::AssignedEcPoint>,
+        normalized: bool,
+    ) -> EcPoint<'a, C, EccChip> {
         let index = *self.num_ec_point.borrow();
         *self.num_ec_point.borrow_mut() += 1;
+        self.ec_point_regions.borrow_mut().push(self.next_region());
         EcPoint {
             loader: self.clone(),
             index,
-            assigned,
+            assigned: Rc::new(RefCell::new(assigned)),
+            normalized: Rc::new(Cell::new(normalized)),
         }
     }
 
-    fn add(
+    pub fn try_add(
         self: &Rc<Self>,
         lhs: &Scalar<'a, C, EccChip>,
         rhs: &Scalar<'a, C, EccChip>,
-    ) -> Scalar<'a, C, EccChip> {
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let output =
+            self.constrain_if_gated(&[lhs.index, rhs.index], &[], || self.add_untraced(lhs, rhs))?;
+        #[cfg(feature = "op_trace")]
+        self.record(Op::Add {
+            output: output.index,
+            lhs: lhs.index,
+            rhs: rhs.index,
+        });
+        Ok(output)
+    }
+
+    fn add_untraced(
+        self: &Rc<Self>,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
         let output = match (&lhs.value, &rhs.value) {
             (Value::Constant(lhs), Value::Constant(rhs)) => Value::Constant(*lhs + rhs),
+            (Value::Assigned(_), Value::Constant(constant)) if *constant == C::Scalar::zero() => {
+                return Ok(lhs.clone())
+            }
+            (Value::Constant(constant), Value::Assigned(_)) if *constant == C::Scalar::zero() => {
+                return Ok(rhs.clone())
+            }
             (Value::Assigned(assigned), Value::Constant(constant))
             | (Value::Constant(constant), Value::Assigned(assigned)) => self
                 .scalar_chip()
@@ -153,7 +1119,7 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
                     *constant,
                 )
                 .map(Value::Assigned)
-                .unwrap(),
+                .map_err(chip_error("add"))?,
             (Value::Assigned(lhs), Value::Assigned(rhs)) => self
                 .scalar_chip()
                 .sum_with_coeff_and_const(
@@ -165,16 +1131,32 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
                     C::Scalar::zero(),
                 )
                 .map(Value::Assigned)
-                .unwrap(),
+                .map_err(chip_error("add"))?,
         };
-        self.scalar(output)
+        Ok(self.scalar(output))
     }
 
-    fn sub(
+    pub fn try_sub(
         self: &Rc<Self>,
         lhs: &Scalar<'a, C, EccChip>,
         rhs: &Scalar<'a, C, EccChip>,
-    ) -> Scalar<'a, C, EccChip> {
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let output =
+            self.constrain_if_gated(&[lhs.index, rhs.index], &[], || self.sub_untraced(lhs, rhs))?;
+        #[cfg(feature = "op_trace")]
+        self.record(Op::Sub {
+            output: output.index,
+            lhs: lhs.index,
+            rhs: rhs.index,
+        });
+        Ok(output)
+    }
+
+    fn sub_untraced(
+        self: &Rc<Self>,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
         let output = match (&lhs.value, &rhs.value) {
             (Value::Constant(lhs), Value::Constant(rhs)) => Value::Constant(*lhs - rhs),
             (Value::Constant(constant), Value::Assigned(assigned)) => self
@@ -185,7 +1167,7 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
                     *constant,
                 )
                 .map(Value::Assigned)
-                .unwrap(),
+                .map_err(chip_error("sub"))?,
             (Value::Assigned(assigned), Value::Constant(constant)) => self
                 .scalar_chip()
                 .sum_with_coeff_and_const(
@@ -194,23 +1176,51 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
                     -*constant,
                 )
                 .map(Value::Assigned)
-                .unwrap(),
+                .map_err(chip_error("sub"))?,
             (Value::Assigned(lhs), Value::Assigned(rhs)) => {
                 IntegerInstructions::sub(self.scalar_chip().deref(), &mut self.ctx_mut(), lhs, rhs)
                     .map(Value::Assigned)
-                    .unwrap()
+                    .map_err(chip_error("sub"))?
             }
         };
-        self.scalar(output)
+        Ok(self.scalar(output))
     }
 
-    fn mul(
+    pub fn try_mul(
         self: &Rc<Self>,
         lhs: &Scalar<'a, C, EccChip>,
         rhs: &Scalar<'a, C, EccChip>,
-    ) -> Scalar<'a, C, EccChip> {
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let output =
+            self.constrain_if_gated(&[lhs.index, rhs.index], &[], || self.mul_untraced(lhs, rhs))?;
+        #[cfg(feature = "op_trace")]
+        self.record(Op::Mul {
+            output: output.index,
+            lhs: lhs.index,
+            rhs: rhs.index,
+        });
+        Ok(output)
+    }
+
+    fn mul_untraced(
+        self: &Rc<Self>,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
         let output = match (&lhs.value, &rhs.value) {
             (Value::Constant(lhs), Value::Constant(rhs)) => Value::Constant(*lhs * rhs),
+            (Value::Assigned(_), Value::Constant(constant))
+            | (Value::Constant(constant), Value::Assigned(_))
+                if *constant == C::Scalar::zero() =>
+            {
+                Value::Constant(C::Scalar::zero())
+            }
+            (Value::Assigned(_), Value::Constant(constant)) if *constant == C::Scalar::one() => {
+                return Ok(lhs.clone())
+            }
+            (Value::Constant(constant), Value::Assigned(_)) if *constant == C::Scalar::one() => {
+                return Ok(rhs.clone())
+            }
             (Value::Assigned(assigned), Value::Constant(constant))
             | (Value::Constant(constant), Value::Assigned(assigned)) => self
                 .scalar_chip()
@@ -220,7 +1230,7 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
                     C::Scalar::zero(),
                 )
                 .map(Value::Assigned)
-                .unwrap(),
+                .map_err(chip_error("mul"))?,
             (Value::Assigned(lhs), Value::Assigned(rhs)) => self
                 .scalar_chip()
                 .sum_products_with_coeff_and_const(
@@ -229,25 +1239,139 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
                     C::Scalar::zero(),
                 )
                 .map(Value::Assigned)
-                .unwrap(),
+                .map_err(chip_error("mul"))?,
         };
-        self.scalar(output)
+        Ok(self.scalar(output))
     }
 
-    fn neg(self: &Rc<Self>, scalar: &Scalar<'a, C, EccChip>) -> Scalar<'a, C, EccChip> {
+    pub fn try_mul_add(
+        self: &Rc<Self>,
+        a: &Scalar<'a, C, EccChip>,
+        b: &Scalar<'a, C, EccChip>,
+        c: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let output = self.constrain_if_gated(&[a.index, b.index, c.index], &[], || {
+            self.mul_add_untraced(a, b, c)
+        })?;
+        #[cfg(feature = "op_trace")]
+        self.record(Op::MulAdd {
+            output: output.index,
+            a: a.index,
+            b: b.index,
+            c: c.index,
+        });
+        Ok(output)
+    }
+
+    fn mul_add_untraced(
+        self: &Rc<Self>,
+        a: &Scalar<'a, C, EccChip>,
+        b: &Scalar<'a, C, EccChip>,
+        c: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let output = match (&a.value, &b.value, &c.value) {
+            (Value::Constant(a), Value::Constant(b), Value::Constant(c)) => {
+                Value::Constant(*a * b + c)
+            }
+            (Value::Constant(a), Value::Constant(b), Value::Assigned(c)) => self
+                .scalar_chip()
+                .sum_with_coeff_and_const(
+                    &mut self.ctx_mut(),
+                    &[(C::Scalar::one(), c.clone())],
+                    *a * b,
+                )
+                .map(Value::Assigned)
+                .map_err(chip_error("mul_add"))?,
+            (Value::Assigned(assigned), Value::Constant(constant), Value::Constant(c))
+            | (Value::Constant(constant), Value::Assigned(assigned), Value::Constant(c)) => self
+                .scalar_chip()
+                .sum_with_coeff_and_const(&mut self.ctx_mut(), &[(*constant, assigned.clone())], *c)
+                .map(Value::Assigned)
+                .map_err(chip_error("mul_add"))?,
+            (Value::Assigned(assigned), Value::Constant(constant), Value::Assigned(c))
+            | (Value::Constant(constant), Value::Assigned(assigned), Value::Assigned(c)) => self
+                .scalar_chip()
+                .sum_with_coeff_and_const(
+                    &mut self.ctx_mut(),
+                    &[(*constant, assigned.clone()), (C::Scalar::one(), c.clone())],
+                    C::Scalar::zero(),
+                )
+                .map(Value::Assigned)
+                .map_err(chip_error("mul_add"))?,
+            (Value::Assigned(a), Value::Assigned(b), Value::Constant(c)) => self
+                .scalar_chip()
+                .sum_products_with_coeff_and_const(
+                    &mut self.ctx_mut(),
+                    &[(C::Scalar::one(), a.clone(), b.clone())],
+                    *c,
+                )
+                .map(Value::Assigned)
+                .map_err(chip_error("mul_add"))?,
+            (Value::Assigned(a), Value::Assigned(b), Value::Assigned(c)) => self
+                .scalar_chip()
+                .mul_add(&mut self.ctx_mut(), a, b, c)
+                .map(Value::Assigned)
+                .map_err(chip_error("mul_add"))?,
+        };
+        Ok(self.scalar(output))
+    }
+
+    pub fn try_neg(
+        self: &Rc<Self>,
+        scalar: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let output = self.constrain_if_gated(&[scalar.index], &[], || self.neg_untraced(scalar))?;
+        #[cfg(feature = "op_trace")]
+        self.record(Op::Neg {
+            output: output.index,
+            scalar: scalar.index,
+        });
+        Ok(output)
+    }
+
+    fn neg_untraced(
+        self: &Rc<Self>,
+        scalar: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
         let output = match &scalar.value {
             Value::Constant(constant) => Value::Constant(constant.neg()),
             Value::Assigned(assigned) => {
                 IntegerInstructions::neg(self.scalar_chip().deref(), &mut self.ctx_mut(), assigned)
                     .map(Value::Assigned)
-                    .unwrap()
+                    .map_err(chip_error("neg"))?
             }
         };
-        self.scalar(output)
+        Ok(self.scalar(output))
     }
 
-    fn invert(self: &Rc<Self>, scalar: &Scalar<'a, C, EccChip>) -> Scalar<'a, C, EccChip> {
+    pub fn try_invert(
+        self: &Rc<Self>,
+        scalar: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let output =
+            self.constrain_if_gated(&[scalar.index], &[], || self.invert_untraced(scalar))?;
+        #[cfg(feature = "op_trace")]
+        self.record(Op::Invert {
+            output: output.index,
+            scalar: scalar.index,
+        });
+        Ok(output)
+    }
+
+    fn invert_untraced(
+        self: &Rc<Self>,
+        scalar: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("invert", index = scalar.index).entered();
+
         let output = match &scalar.value {
+            Value::Constant(constant) if *constant == C::Scalar::zero() => {
+                return Err(crate::Error::ChipError {
+                    op: "invert",
+                    source: "Inversion of zero".to_string(),
+                })
+            }
             Value::Constant(constant) => Value::Constant(Field::invert(constant).unwrap()),
             Value::Assigned(assigned) => Value::Assigned(
                 IntegerInstructions::invert(
@@ -255,29 +1379,552 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, Ecc
                     &mut self.ctx_mut(),
                     assigned,
                 )
-                .unwrap(),
+                .map_err(chip_error("invert"))?,
             ),
         };
-        self.scalar(output)
+        Ok(self.scalar(output))
+    }
+
+    pub fn try_div(
+        self: &Rc<Self>,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let output =
+            self.constrain_if_gated(&[lhs.index, rhs.index], &[], || self.div_untraced(lhs, rhs))?;
+        #[cfg(feature = "op_trace")]
+        self.record(Op::Div {
+            output: output.index,
+            lhs: lhs.index,
+            rhs: rhs.index,
+        });
+        Ok(output)
+    }
+
+    fn div_untraced(
+        self: &Rc<Self>,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let division_by_zero = || crate::Error::ChipError {
+            op: "div",
+            source: "Division by zero".to_string(),
+        };
+        let output = match (&lhs.value, &rhs.value) {
+            (_, Value::Constant(constant)) if *constant == C::Scalar::zero() => {
+                return Err(division_by_zero())
+            }
+            (Value::Constant(lhs), Value::Constant(rhs)) => {
+                Value::Constant(*lhs * Field::invert(rhs).unwrap())
+            }
+            (Value::Assigned(assigned), Value::Constant(constant)) => self
+                .scalar_chip()
+                .sum_with_coeff_and_const(
+                    &mut self.ctx_mut(),
+                    &[(Field::invert(constant).unwrap(), assigned.clone())],
+                    C::Scalar::zero(),
+                )
+                .map(Value::Assigned)
+                .map_err(chip_error("div"))?,
+            (Value::Constant(_), Value::Assigned(_)) => {
+                return self.mul_untraced(lhs, &self.invert_untraced(rhs)?)
+            }
+            (Value::Assigned(lhs), Value::Assigned(rhs)) => self
+                .scalar_chip()
+                .div(&mut self.ctx_mut(), lhs, rhs)
+                .map(Value::Assigned)
+                .map_err(chip_error("div"))?,
+        };
+        Ok(self.scalar(output))
+    }
+
+    /// Checks that the last `multi_scalar_multiplication` was given
+    /// `expected` terms, including ones skipped for being identity, to catch
+    /// mismatches between absorbed commitments and msm terms.
+    pub fn assert_msm_arity(&self, expected: usize) -> Result<(), crate::Error> {
+        match self.last_msm_arity.get() {
+            Some(arity) if arity == expected => Ok(()),
+            Some(arity) => Err(crate::Error::AssertionFailureDetailed {
+                annotation: "msm arity".to_string(),
+                detail: format!("Expected {} terms but last msm had {}", expected, arity),
+            }),
+            None => Err(crate::Error::AssertionFailure(
+                "No msm to check arity of".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the number of `msm`s and the rows they took so far, keyed by
+    /// their number of terms. Identity outputs of terms that cancel out
+    /// entirely take no rows and aren't counted.
+    pub fn msm_histogram(&self) -> BTreeMap<usize, (usize, usize)> {
+        self.msm_histogram.borrow().clone()
+    }
+
+    /// Computes `sum(scalar * ec_point)`. Terms are processed in order of
+    /// point index and then scalar index, regardless of the order of `pairs`,
+    /// so the layout only depends on which terms are given.
+    ///
+    /// The output is left unnormalized, and only normalized once its cells
+    /// are needed, e.g. as a scaled term of another `msm` or to be exposed as
+    /// instances, while terms with unit scalar are added as they are.
+    pub fn try_multi_scalar_multiplication(
+        self: &Rc<Self>,
+        pairs: Vec<(Scalar<'a, C, EccChip>, EcPoint<'a, C, EccChip>)>,
+    ) -> Result<EcPoint<'a, C, EccChip>, crate::Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("msm", num_terms = pairs.len()).entered();
+        self.last_msm_arity.set(Some(pairs.len()));
+        let arity = pairs.len();
+        let offset = self.ctx().offset();
+        #[cfg(feature = "op_trace")]
+        let traced_pairs = pairs
+            .iter()
+            .map(|(scalar, ec_point)| (scalar.index, ec_point.index))
+            .collect_vec();
+
+        let pairs = pairs
+            .into_iter()
+            .filter(|(_, ec_point)| !ec_point.is_identity_const())
+            .sorted_by_key(|(scalar, ec_point)| (ec_point.index, scalar.index))
+            .collect_vec();
+        if pairs.is_empty() {
+            let output = self.ec_point_identity();
+            #[cfg(feature = "op_trace")]
+            self.record(Op::Msm {
+                output: output.index,
+                pairs: traced_pairs,
+            });
+            return Ok(output);
+        }
+
+        let (scalars, ec_points): (Vec<_>, Vec<_>) = pairs
+            .iter()
+            .map(|(scalar, ec_point)| (scalar.index, ec_point.index))
+            .unzip();
+        let strategy = self.msm_strategy.borrow().clone();
+        let output = self.constrain_if_gated(&scalars, &ec_points, || strategy.msm(self, pairs))?;
+        {
+            let rows = self.ctx().offset() - offset;
+            let mut msm_histogram = self.msm_histogram.borrow_mut();
+            let (count, total) = msm_histogram.entry(arity).or_default();
+            *count += 1;
+            *total += rows;
+        }
+        #[cfg(feature = "op_trace")]
+        self.record(Op::Msm {
+            output: output.index,
+            pairs: traced_pairs,
+        });
+        Ok(output)
+    }
+
+    /// Computes `sum(scalar * ec_point)` of non-identity points by handing
+    /// the terms with non-trivial scalars to the chip's `msm` and adding the
+    /// rest on top, which is what [`DefaultMsmStrategy`] does.
+    pub fn split_multi_scalar_multiplication(
+        self: &Rc<Self>,
+        pairs: Vec<(Scalar<'a, C, EccChip>, EcPoint<'a, C, EccChip>)>,
+    ) -> Result<EcPoint<'a, C, EccChip>, crate::Error> {
+        let (mut non_scaled, mut scaled) = (Vec::new(), Vec::new());
+        for (scalar, ec_point) in pairs.iter() {
+            match scalar.value {
+                Value::Constant(constant) if constant == C::Scalar::one() => {
+                    non_scaled.push(ec_point.assigned_unnormalized()?);
+                }
+                // Constant scalars go through the chip's `mul_const` when it
+                // has one, and join the msm otherwise
+                Value::Constant(constant) if self.ecc_chip().supports_mul_const() => {
+                    let assigned = ec_point.assigned()?;
+                    let output = self
+                        .ecc_chip()
+                        .mul_const(&mut self.ctx_mut(), &assigned, constant)
+                        .map_err(chip_error("mul_const"))?;
+                    non_scaled.push(output);
+                }
+                Value::Constant(_) | Value::Assigned(_) => {
+                    scaled.push((ec_point.assigned()?, scalar.assigned()))
+                }
+            }
+        }
+
+        // Scaled terms are split into chunks the chip accepts, whose partial
+        // sums are added up together with the non-scaled terms
+        let scaled = scaled
+            .chunks(self.max_msm_terms())
+            .map(|chunk| {
+                self.ecc_chip
+                    .borrow_mut()
+                    .multi_scalar_multiplication(&mut self.ctx_mut(), chunk.to_vec())
+                    .map_err(chip_error("msm"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let complete_addition = self.complete_addition.get();
+        let output = scaled
+            .into_iter()
+            .chain(non_scaled)
+            .map(Ok)
+            .reduce(|acc, ec_point| {
+                let (acc, ec_point) = (acc?, ec_point?);
+                let ecc_chip = self.ecc_chip();
+                let ctx = &mut self.ctx_mut();
+                if complete_addition {
+                    let acc = ecc_chip.normalize(ctx, &acc)?;
+                    let ec_point = ecc_chip.normalize(ctx, &ec_point)?;
+                    ecc_chip.add_complete(ctx, &acc, &ec_point)
+                } else {
+                    ecc_chip.add_projective(ctx, &acc, &ec_point)
+                }
+            })
+            .unwrap()
+            .map_err(chip_error("msm"))?;
+
+        Ok(self.ec_point_with_normalized(Some(output), false))
+    }
+
+    fn add(
+        self: &Rc<Self>,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Scalar<'a, C, EccChip> {
+        self.try_add(lhs, rhs).unwrap()
+    }
+
+    fn sub(
+        self: &Rc<Self>,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Scalar<'a, C, EccChip> {
+        self.try_sub(lhs, rhs).unwrap()
+    }
+
+    fn mul(
+        self: &Rc<Self>,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Scalar<'a, C, EccChip> {
+        self.try_mul(lhs, rhs).unwrap()
+    }
+
+    fn mul_add(
+        self: &Rc<Self>,
+        a: &Scalar<'a, C, EccChip>,
+        b: &Scalar<'a, C, EccChip>,
+        c: &Scalar<'a, C, EccChip>,
+    ) -> Scalar<'a, C, EccChip> {
+        self.try_mul_add(a, b, c).unwrap()
+    }
+
+    fn div(
+        self: &Rc<Self>,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Scalar<'a, C, EccChip> {
+        self.try_div(lhs, rhs).unwrap()
+    }
+
+    fn neg(self: &Rc<Self>, scalar: &Scalar<'a, C, EccChip>) -> Scalar<'a, C, EccChip> {
+        self.try_neg(scalar).unwrap()
+    }
+
+    fn invert(self: &Rc<Self>, scalar: &Scalar<'a, C, EccChip>) -> Scalar<'a, C, EccChip> {
+        self.try_invert(scalar).unwrap()
+    }
+
+    fn batch_invert<'b>(self: &Rc<Self>, values: Vec<&'b mut Scalar<'a, C, EccChip>>)
+    where
+        'a: 'b,
+    {
+        let (constants, assigned): (Vec<_>, Vec<_>) = values
+            .into_iter()
+            .partition(|value| matches!(value.value, Value::Constant(_)));
+
+        // Zero has no inverse and is left as is, as `util::arithmetic` does
+        for value in constants {
+            if let Ok(inverted) = self.try_invert(value) {
+                *value = inverted;
+            }
+        }
+
+        // Inverses of known values are computed all at once ahead of the
+        // assignment, so the chip only places the precomputed witnesses.
+        // Zero is given zero as witness, which fails the constraint.
+        let inputs = assigned.iter().map(|value| value.assigned()).collect_vec();
+        let witnesses = inputs
+            .iter()
+            .map(|input| self.scalar_chip().value_of(input))
+            .collect::<circuit::Value<Vec<_>>>()
+            .map(|mut values| {
+                batch_invert_witnesses(&mut values);
+                values
+            })
+            .transpose_vec(inputs.len());
+        for ((value, input), witness) in assigned.into_iter().zip(inputs).zip(witnesses) {
+            let output = self
+                .scalar_chip()
+                .invert_with_witness(&mut self.ctx_mut(), &input, witness)
+                .unwrap();
+            self.mark_constrained(&[value.index], &[]);
+            *value = self.scalar(Value::Assigned(output));
+        }
+    }
+
+    /// Adds each pair like [`Halo2Loader::try_add`], with the sums of
+    /// assigned pairs computed all at once ahead of the assignment.
+    pub fn batch_add(
+        self: &Rc<Self>,
+        pairs: &[(Scalar<'a, C, EccChip>, Scalar<'a, C, EccChip>)],
+    ) -> Result<Vec<Scalar<'a, C, EccChip>>, crate::Error> {
+        self.batch_binary_op(pairs, BinaryOp::Add)
+    }
+
+    /// Multiplies each pair like [`Halo2Loader::try_mul`], with the products
+    /// of assigned pairs computed all at once ahead of the assignment.
+    pub fn batch_mul(
+        self: &Rc<Self>,
+        pairs: &[(Scalar<'a, C, EccChip>, Scalar<'a, C, EccChip>)],
+    ) -> Result<Vec<Scalar<'a, C, EccChip>>, crate::Error> {
+        self.batch_binary_op(pairs, BinaryOp::Mul)
+    }
+
+    fn batch_binary_op(
+        self: &Rc<Self>,
+        pairs: &[(Scalar<'a, C, EccChip>, Scalar<'a, C, EccChip>)],
+        op: BinaryOp,
+    ) -> Result<Vec<Scalar<'a, C, EccChip>>, crate::Error> {
+        let assigned = pairs
+            .iter()
+            .filter_map(|(lhs, rhs)| match (&lhs.value, &rhs.value) {
+                (Value::Assigned(lhs), Value::Assigned(rhs)) => Some((lhs, rhs)),
+                _ => None,
+            })
+            .collect_vec();
+        let witnesses = {
+            let scalar_chip = self.scalar_chip();
+            assigned
+                .iter()
+                .map(|(lhs, rhs)| scalar_chip.value_of(lhs).zip(scalar_chip.value_of(rhs)))
+                .collect::<circuit::Value<Vec<_>>>()
+                .map(|pairs| match op {
+                    BinaryOp::Add => batch_witnesses(pairs, |lhs, rhs| lhs + rhs),
+                    BinaryOp::Mul => batch_witnesses(pairs, |lhs, rhs| lhs * rhs),
+                })
+                .transpose_vec(assigned.len())
+        };
+
+        let mut witnesses = witnesses.into_iter();
+        pairs
+            .iter()
+            .map(|(lhs, rhs)| match (&lhs.value, &rhs.value) {
+                (Value::Assigned(lhs_assigned), Value::Assigned(rhs_assigned)) => {
+                    let witness = witnesses.next().unwrap();
+                    let output = {
+                        let scalar_chip = self.scalar_chip();
+                        let mut ctx = self.ctx_mut();
+                        match op {
+                            BinaryOp::Add => scalar_chip
+                                .add_with_witness(&mut ctx, lhs_assigned, rhs_assigned, witness)
+                                .map_err(chip_error("add"))?,
+                            BinaryOp::Mul => scalar_chip
+                                .mul_with_witness(&mut ctx, lhs_assigned, rhs_assigned, witness)
+                                .map_err(chip_error("mul"))?,
+                        }
+                    };
+                    self.mark_constrained(&[lhs.index, rhs.index], &[]);
+                    Ok(self.scalar(Value::Assigned(output)))
+                }
+                _ => match op {
+                    BinaryOp::Add => self.try_add(lhs, rhs),
+                    BinaryOp::Mul => self.try_mul(lhs, rhs),
+                },
+            })
+            .collect()
+    }
+
+    /// Recodes `scalar` into a regular wNAF, whose digits are all odd and
+    /// never zero so every window costs the same rows. Windows have
+    /// `window_size` bits, least significant first, where a window of value
+    /// `u` stands for the digit `2 * u + 1 - 2^window_size`. Even scalars are recoded as `scalar + r`
+    /// to keep every digit odd, which is the same scalar modulo `r`. Digits
+    /// are constrained to compose `scalar` and windows are returned as their
+    /// constrained bits.
+    pub(crate) fn signed_windows(
+        self: &Rc<Self>,
+        scalar: &Scalar<'a, C, EccChip>,
+        window_size: usize,
+    ) -> Result<Vec<Vec<Scalar<'a, C, EccChip>>>, crate::Error> {
+        // Top window is left with the digit one once `scalar + r` is consumed
+        let num_windows = (C::Scalar::NUM_BITS as usize + window_size) / window_size + 1;
+        let digits = scalar
+            .to_native()
+            .map(|scalar| signed_window_digits(scalar, window_size, num_windows))
+            .transpose_vec(num_windows);
+        let windows = {
+            let scalar_chip = self.scalar_chip();
+            digits
+                .into_iter()
+                .map(|digit| digit.map(|digit| scalar_chip.integer(C::Scalar::from(digit))))
+                .collect_vec()
+        };
+        let windows = self.assign_scalars(windows);
+
+        let shift = C::Scalar::from(1u64 << window_size);
+        let coeffs = iter::successors(Some(C::Scalar::from(2)), |coeff| Some(*coeff * shift));
+        let composed = self.sum_with_coeff_and_const(
+            &coeffs.zip(windows.iter()).collect_vec(),
+            C::Scalar::one() - shift.pow_vartime(&[num_windows as u64]),
+        );
+        self.assert_eq("signed_windows", &composed, scalar)?;
+
+        windows
+            .iter()
+            .map(|window| self.to_bits(window, window_size))
+            .collect()
+    }
+}
+
+/// Window size of [`EcPoint::mul`], whose table has `2^SIGNED_WINDOW_SIZE`
+/// odd multiples of the point.
+const SIGNED_WINDOW_SIZE: usize = 4;
+
+fn chip_error(op: &'static str) -> impl Fn(plonk::Error) -> crate::Error {
+    move |err| crate::Error::ChipError {
+        op,
+        source: format!("{:?}", err),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum BinaryOp {
+    Add,
+    Mul,
+}
+
+/// Inverts `values` in place with Montgomery's trick, run per chunk across
+/// threads when `parallel` is enabled. Zeros are left as zero.
+fn batch_invert_witnesses<F: PrimeField>(values: &mut [F]) {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        let chunk_size = (values.len() / rayon::current_num_threads()).max(1);
+        values
+            .par_chunks_mut(chunk_size)
+            .for_each(batch_invert_non_zero);
+    }
+    #[cfg(not(feature = "parallel"))]
+    batch_invert_non_zero(values);
+}
+
+fn batch_invert_non_zero<F: PrimeField>(values: &mut [F]) {
+    let mut acc = F::one();
+    let products = values
+        .iter()
+        .map(|value| {
+            let product = acc;
+            if !value.is_zero_vartime() {
+                acc *= value;
+            }
+            product
+        })
+        .collect_vec();
+
+    // Product of non-zero values is never zero
+    let mut acc_inv = Field::invert(&acc).unwrap_or(F::zero());
+    for (value, product) in values.iter_mut().zip(products).rev() {
+        if !value.is_zero_vartime() {
+            let inv = acc_inv * product;
+            acc_inv *= *value;
+            *value = inv;
+        }
     }
 }
 
+/// Returns window values `u_i` of the odd digits `2 * u_i + 1 - 2^window_size`
+/// composing `scalar`, or `scalar + r` when `scalar` is even.
+fn signed_window_digits<F: PrimeField>(
+    scalar: F,
+    window_size: usize,
+    num_windows: usize,
+) -> Vec<u64> {
+    let mut k = fe_to_big(scalar);
+    if !k.bit(0) {
+        k += modulus::<F>();
+    }
+    let half = BigUint::from(1u64) << window_size;
+    let full = BigUint::from(1u64) << (window_size + 1);
+    let to_u64 = |value: BigUint| value.iter_u64_digits().next().unwrap_or(0);
+
+    // Each odd digit leaves an odd remainder, which ends up below the window
+    let mut digits = Vec::with_capacity(num_windows);
+    for _ in 0..num_windows - 1 {
+        let rem = &k % &full;
+        k = (k + &half - &rem) >> window_size;
+        digits.push(to_u64(rem - 1u64) / 2);
+    }
+    digits.push(to_u64(k + &half - 1u64) / 2);
+    digits
+}
+
+/// Computes `op` of each pair of known values, across threads when
+/// `parallel` is enabled.
+fn batch_witnesses<F: PrimeField>(pairs: Vec<(F, F)>, op: fn(F, F) -> F) -> Vec<F> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        pairs
+            .into_par_iter()
+            .map(|(lhs, rhs)| op(lhs, rhs))
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    pairs.into_iter().map(|(lhs, rhs)| op(lhs, rhs)).collect()
+}
+
 #[cfg(test)]
 impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Halo2Loader<'a, C, EccChip> {
+    /// Starts metering into the bucket of `identifier`, which is only
+    /// copied into an owned `Cow` the first time it is seen, so repeated
+    /// meterings of the same identifier don't allocate.
     fn start_row_metering(self: &Rc<Self>, identifier: &str) {
-        use crate::loader::halo2::shim::Context;
-
-        self.row_meterings
+        let mut row_meterings = self.row_meterings.borrow_mut();
+        let bucket = match row_meterings
+            .iter()
+            .position(|(exist, _)| exist == identifier)
+        {
+            Some(bucket) => bucket,
+            None => {
+                row_meterings.push((Cow::Owned(identifier.to_string()), 0));
+                row_meterings.len() - 1
+            }
+        };
+        self.row_metering_starts
             .borrow_mut()
-            .push((identifier.to_string(), self.ctx().offset()))
+            .push((bucket, self.ctx().offset()))
     }
 
+    /// Adds rows used since the matching start to its bucket, so repeated
+    /// meterings of the same identifier accumulate.
     fn end_row_metering(self: &Rc<Self>) {
-        use crate::loader::halo2::shim::Context;
+        let (bucket, start) = self.row_metering_starts.borrow_mut().pop().unwrap();
+        self.row_meterings.borrow_mut()[bucket].1 += self.ctx().offset() - start;
+    }
 
-        let mut row_meterings = self.row_meterings.borrow_mut();
-        let (_, row) = row_meterings.last_mut().unwrap();
-        *row = self.ctx().offset() - *row;
+    pub(crate) fn row_metering(&self, identifier: &str) -> Option<usize> {
+        self.row_meterings
+            .borrow()
+            .iter()
+            .find(|(exist, _)| exist == identifier)
+            .map(|(_, cost)| *cost)
+    }
+
+    /// Returns the capacities of the scalar and point bookkeeping.
+    pub(crate) fn capacities(&self) -> (usize, usize) {
+        (
+            self.scalar_regions.borrow().capacity(),
+            self.ec_point_regions.borrow().capacity(),
+        )
     }
 
     pub fn print_row_metering(self: &Rc<Self>) {
@@ -293,23 +1940,275 @@ pub enum Value<T, L> {
     Assigned(L),
 }
 
-#[derive(Clone)]
-pub struct Scalar<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> {
-    loader: Rc<Halo2Loader<'a, C, EccChip>>,
-    index: usize,
-    value: Value<C::Scalar, EccChip::AssignedScalar>,
+#[derive(Clone)]
+pub struct Scalar<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> {
+    loader: Rc<Halo2Loader<'a, C, EccChip>>,
+    index: usize,
+    value: Value<C::Scalar, EccChip::AssignedScalar>,
+}
+
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Scalar<'a, C, EccChip> {
+    pub fn loader(&self) -> &Rc<Halo2Loader<'a, C, EccChip>> {
+        &self.loader
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns a scalar of a new index assigned in a fresh cell, which is
+    /// constrained to be equal to this one.
+    pub fn copy_to_new_cell(&self) -> Self {
+        let copied = match &self.value {
+            Value::Constant(constant) => return self.loader.assign_const_scalar(*constant),
+            Value::Assigned(assigned) => self
+                .loader
+                .scalar_chip()
+                .copy(&mut self.loader.ctx_mut(), assigned)
+                .unwrap(),
+        };
+        self.loader.scalar(Value::Assigned(copied))
+    }
+
+    /// Marks the scalar to be exposed at `row` of the instance column, which
+    /// is constrained when the outputs taken by
+    /// [`Halo2Loader::take_public_outputs`] are exposed. Until then the
+    /// scalar doesn't count as constrained.
+    pub fn expose_public(&self, row: usize) -> Result<(), crate::Error> {
+        if self
+            .loader
+            .public_outputs
+            .borrow()
+            .iter()
+            .any(|(exist, _, _)| *exist == row)
+        {
+            return Err(crate::Error::AssertionFailure(format!(
+                "Row {} of instance column is already exposed",
+                row
+            )));
+        }
+        let assigned = self.assigned();
+        self.loader
+            .public_outputs
+            .borrow_mut()
+            .push((row, self.index, assigned));
+        Ok(())
+    }
+
+    /// Returns the cell holding the scalar, for gadgets written against the
+    /// `Layouter` API, which fails when the scalar chip is not native.
+    pub fn into_native_cell(
+        &self,
+    ) -> Result<circuit::AssignedCell<C::Scalar, C::Scalar>, crate::Error> {
+        let assigned = self.assigned();
+        self.loader
+            .scalar_chip()
+            .native_cell(&assigned)
+            .ok_or_else(|| crate::Error::ChipError {
+                op: "into_native_cell",
+                source: "Scalar chip doesn't hold integers in a single native cell".to_string(),
+            })
+    }
+
+    /// Returns the values of the scalar's limbs, least significant first, as
+    /// split by the scalar chip. Constants are split without being assigned.
+    pub fn limbs(&self) -> Vec<circuit::Value<C::Scalar>> {
+        match &self.value {
+            Value::Constant(constant) => {
+                let LimbInfo {
+                    num_limbs,
+                    bits_per_limb,
+                } = self.loader.scalar_limb_info();
+                let big = fe_to_big(*constant);
+                let mask = (BigUint::from(1u64) << bits_per_limb) - 1u64;
+                (0..num_limbs)
+                    .map(|idx| {
+                        let limb = (&big >> (idx * bits_per_limb)) & &mask;
+                        circuit::Value::known(fe_from_big(limb))
+                    })
+                    .collect()
+            }
+            Value::Assigned(assigned) => self.loader.scalar_chip().limbs(assigned),
+        }
+    }
+
+    /// Returns the scalar in its canonical limb representation, which is the
+    /// scalar itself for constants.
+    pub fn reduce(&self) -> Self {
+        let reduced = match &self.value {
+            Value::Constant(_) => return self.clone(),
+            Value::Assigned(assigned) => self
+                .loader
+                .scalar_chip()
+                .reduce(&mut self.loader.ctx_mut(), assigned)
+                .unwrap(),
+        };
+        self.loader.scalar(Value::Assigned(reduced))
+    }
+
+    pub(crate) fn assigned(&self) -> EccChip::AssignedScalar {
+        match &self.value {
+            Value::Constant(constant) => self.loader.assign_const_scalar(*constant).assigned(),
+            Value::Assigned(assigned) => assigned.clone(),
+        }
+    }
+
+    pub fn to_native(&self) -> circuit::Value<C::Scalar> {
+        match &self.value {
+            Value::Constant(constant) => circuit::Value::known(*constant),
+            Value::Assigned(assigned) => self.loader.scalar_chip().value_of(assigned),
+        }
+    }
+
+    /// Returns the value of a constant, or the value of an assigned scalar
+    /// when it's known (e.g. when proving), otherwise `None`.
+    pub fn snapshot(&self) -> Option<C::Scalar> {
+        let mut snapshot = None;
+        self.to_native().map(|value| snapshot = Some(value));
+        snapshot
+    }
+}
+
+/// Snapshots of scalars encoded as their little-endian representation, to be
+/// compared across runs for catching changes of intermediate values.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot(Vec<Option<Vec<u8>>>);
+
+impl Snapshot {
+    pub fn push<F: PrimeField>(&mut self, value: Option<F>) {
+        self.0
+            .push(value.map(|value| value.to_repr().as_ref().to_vec()));
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a, 'b, C: CurveAffine, EccChip: EccInstructions<'a, C>>
+    FromIterator<&'b Scalar<'a, C, EccChip>> for Snapshot
+{
+    fn from_iter<I: IntoIterator<Item = &'b Scalar<'a, C, EccChip>>>(iter: I) -> Self {
+        let mut snapshot = Snapshot::default();
+        for scalar in iter {
+            snapshot.push(scalar.snapshot());
+        }
+        snapshot
+    }
+}
+
+/// Accumulator of `coeff * scalar` terms and a constant, which are summed up
+/// by a single `sum_with_coeff_and_const` on [`ScalarAccumulator::finalize`]
+/// instead of an `add` per term.
+#[derive(Clone, Debug)]
+pub struct ScalarAccumulator<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> {
+    loader: Rc<Halo2Loader<'a, C, EccChip>>,
+    terms: Vec<(C::Scalar, Scalar<'a, C, EccChip>)>,
+    constant: C::Scalar,
+}
+
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> ScalarAccumulator<'a, C, EccChip> {
+    pub fn new(loader: &Rc<Halo2Loader<'a, C, EccChip>>) -> Self {
+        Self {
+            loader: loader.clone(),
+            terms: Vec::new(),
+            constant: C::Scalar::zero(),
+        }
+    }
+
+    /// Adds `coeff * scalar`, where constant scalars are folded into the
+    /// constant.
+    pub fn push(&mut self, coeff: C::Scalar, scalar: &Scalar<'a, C, EccChip>) {
+        match &scalar.value {
+            Value::Constant(constant) => self.constant += coeff * constant,
+            Value::Assigned(_) => self.terms.push((coeff, scalar.clone())),
+        }
+    }
+
+    pub fn add_const(&mut self, constant: C::Scalar) {
+        self.constant += constant;
+    }
+
+    pub fn finalize(self) -> Scalar<'a, C, EccChip> {
+        let terms = self
+            .terms
+            .iter()
+            .map(|(coeff, scalar)| (*coeff, scalar))
+            .collect_vec();
+        self.loader.sum_with_coeff_and_const(&terms, self.constant)
+    }
+}
+
+impl<'a, 'b, C: CurveAffine, EccChip: EccInstructions<'a, C>> AddAssign<&'b Scalar<'a, C, EccChip>>
+    for ScalarAccumulator<'a, C, EccChip>
+{
+    fn add_assign(&mut self, rhs: &'b Scalar<'a, C, EccChip>) {
+        self.push(C::Scalar::one(), rhs);
+    }
+}
+
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> AddAssign<Scalar<'a, C, EccChip>>
+    for ScalarAccumulator<'a, C, EccChip>
+{
+    fn add_assign(&mut self, rhs: Scalar<'a, C, EccChip>) {
+        self.push(C::Scalar::one(), &rhs);
+    }
+}
+
+/// Witness scalar that is only assigned when first used, so branches of a
+/// verifier that never consume it don't spend any rows on it.
+#[derive(Clone)]
+pub struct LazyScalar<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> {
+    loader: Rc<Halo2Loader<'a, C, EccChip>>,
+    value: circuit::Value<EccChip::Scalar>,
+    scalar: Rc<RefCell<Option<Scalar<'a, C, EccChip>>>>,
+}
+
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> LazyScalar<'a, C, EccChip> {
+    /// Returns the assigned scalar, assigning it on the first call.
+    pub fn scalar(&self) -> Scalar<'a, C, EccChip> {
+        self.scalar
+            .borrow_mut()
+            .get_or_insert_with(|| self.loader.assign_scalar(self.value.clone()))
+            .clone()
+    }
+
+    pub fn is_assigned(&self) -> bool {
+        self.scalar.borrow().is_some()
+    }
+}
+
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Debug for LazyScalar<'a, C, EccChip> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyScalar")
+            .field("value", &self.value)
+            .field("scalar", &self.scalar)
+            .finish()
+    }
+}
+
+/// Extracts the native value of a loaded scalar, so the same computation can
+/// be cross-checked between `NativeLoader` and `Halo2Loader`.
+pub trait ToNative<F: PrimeField> {
+    fn to_native(&self) -> circuit::Value<F>;
 }
 
-impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Scalar<'a, C, EccChip> {
-    pub fn loader(&self) -> &Rc<Halo2Loader<'a, C, EccChip>> {
-        &self.loader
+impl<F: PrimeField> ToNative<F> for F {
+    fn to_native(&self) -> circuit::Value<F> {
+        circuit::Value::known(*self)
     }
+}
 
-    pub(crate) fn assigned(&self) -> EccChip::AssignedScalar {
-        match &self.value {
-            Value::Constant(constant) => self.loader.assign_const_scalar(*constant).assigned(),
-            Value::Assigned(assigned) => assigned.clone(),
-        }
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> ToNative<C::Scalar>
+    for Scalar<'a, C, EccChip>
+{
+    fn to_native(&self) -> circuit::Value<C::Scalar> {
+        Scalar::to_native(self)
     }
 }
 
@@ -319,6 +2218,16 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> PartialEq for Scalar<'
     }
 }
 
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Eq for Scalar<'a, C, EccChip> {}
+
+/// Hashes only the index, so it reflects identity of the loaded element
+/// rather than its value, consistent with `PartialEq`.
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Hash for Scalar<'a, C, EccChip> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
 impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> LoadedScalar<C::Scalar>
     for Scalar<'a, C, EccChip>
 {
@@ -327,6 +2236,20 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> LoadedScalar<C::Scalar
     fn loader(&self) -> &Self::Loader {
         &self.loader
     }
+
+    fn muladd(&self, b: &Self, c: &Self) -> Self {
+        Halo2Loader::mul_add(&self.loader, self, b, c)
+    }
+
+    fn batch_invert<'b>(values: impl IntoIterator<Item = &'b mut Self>)
+    where
+        Self: 'b,
+    {
+        let values = values.into_iter().collect_vec();
+        if let Some(loader) = values.first().map(|value| value.loader.clone()) {
+            loader.batch_invert(values);
+        }
+    }
 }
 
 impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Debug for Scalar<'a, C, EccChip> {
@@ -405,6 +2328,24 @@ impl<'a, 'b, C: CurveAffine, EccChip: EccInstructions<'a, C>> Mul<&'b Self>
     }
 }
 
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Div for Scalar<'a, C, EccChip> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Halo2Loader::div(&self.loader, &self, &rhs)
+    }
+}
+
+impl<'a, 'b, C: CurveAffine, EccChip: EccInstructions<'a, C>> Div<&'b Self>
+    for Scalar<'a, C, EccChip>
+{
+    type Output = Self;
+
+    fn div(self, rhs: &'b Self) -> Self::Output {
+        Halo2Loader::div(&self.loader, &self, rhs)
+    }
+}
+
 impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> AddAssign for Scalar<'a, C, EccChip> {
     fn add_assign(&mut self, rhs: Self) {
         *self = Halo2Loader::add(&self.loader, self, &rhs)
@@ -451,12 +2392,281 @@ impl<'a, 'b, C: CurveAffine, EccChip: EccInstructions<'a, C>> MulAssign<&'b Self
 pub struct EcPoint<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> {
     loader: Rc<Halo2Loader<'a, C, EccChip>>,
     index: usize,
-    assigned: EccChip::AssignedEcPoint,
+    assigned: Rc<RefCell<Option<EccChip::AssignedEcPoint>>>,
+    normalized: Rc<Cell<bool>>,
 }
 
 impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> EcPoint<'a, C, EccChip> {
-    pub fn assigned(&self) -> EccChip::AssignedEcPoint {
-        self.assigned.clone()
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the normalized assigned point, normalizing it first if it's
+    /// still in its deferred representation.
+    pub fn assigned(&self) -> Result<EccChip::AssignedEcPoint, crate::Error> {
+        self.normalize_if_needed();
+        self.assigned_unnormalized()
+    }
+
+    pub fn is_identity(&self) -> Scalar<'a, C, EccChip> {
+        match self.assigned.borrow().as_ref() {
+            Some(assigned) => {
+                let is_identity = self
+                    .loader
+                    .constrain_if_gated(&[], &[self.index], || {
+                        self.loader
+                            .ecc_chip()
+                            .is_identity(&mut self.loader.ctx_mut(), assigned)
+                    })
+                    .unwrap();
+                self.loader.scalar(Value::Assigned(is_identity))
+            }
+            None => self.loader.load_one(),
+        }
+    }
+
+    /// Returns the x-coordinate cast into the scalar field.
+    pub fn x(&self) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let assigned = self.assigned()?;
+        let x = self
+            .loader
+            .constrain_if_gated(&[], &[self.index], || {
+                self.loader
+                    .ecc_chip()
+                    .x_coordinate(&mut self.loader.ctx_mut(), &assigned)
+            })
+            .map_err(chip_error("x_coordinate"))?;
+        Ok(self.loader.scalar(Value::Assigned(x)))
+    }
+
+    /// Returns the y-coordinate cast into the scalar field.
+    pub fn y(&self) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        let assigned = self.assigned()?;
+        let y = self
+            .loader
+            .constrain_if_gated(&[], &[self.index], || {
+                self.loader
+                    .ecc_chip()
+                    .y_coordinate(&mut self.loader.ctx_mut(), &assigned)
+            })
+            .map_err(chip_error("y_coordinate"))?;
+        Ok(self.loader.scalar(Value::Assigned(y)))
+    }
+
+    /// Multiplies the point by `scalar` without the general `msm`. The
+    /// scalar is recoded by [`Halo2Loader::signed_windows`] into odd digits,
+    /// and the chip adds the matching odd multiple of the point per window
+    /// with `add_window`, so no aux generator is needed. Chips without
+    /// `add_window` go through `msm` instead. With odd digits only the final
+    /// sum can be the identity, so it fails for an assigned zero scalar, whose
+    /// product the chip can't represent.
+    pub fn mul(&self, scalar: &Scalar<'a, C, EccChip>) -> Result<Self, crate::Error> {
+        match scalar.value {
+            Value::Constant(constant) if constant == C::Scalar::zero() => {
+                return Ok(self.loader.ec_point_identity())
+            }
+            Value::Constant(constant) if constant == C::Scalar::one() => return Ok(self.clone()),
+            _ => {}
+        }
+        if self.is_identity_const() {
+            return Ok(self.clone());
+        }
+        if !self.loader.ecc_chip().supports_add_window() {
+            return self
+                .loader
+                .try_multi_scalar_multiplication(vec![(scalar.clone(), self.clone())]);
+        }
+
+        let windows = self.loader.signed_windows(scalar, SIGNED_WINDOW_SIZE)?;
+        let table = self.signed_odd_multiples(SIGNED_WINDOW_SIZE)?;
+        let mut acc = None;
+        for window in windows.iter().rev() {
+            let bits = window.iter().map(|bit| bit.assigned()).collect_vec();
+            let output = self
+                .loader
+                .ecc_chip()
+                .add_window(&mut self.loader.ctx_mut(), acc.as_ref(), &table, &bits)
+                .map_err(chip_error("add_window"))?;
+            acc = Some(output);
+        }
+        self.loader.mark_constrained(&[], &[self.index]);
+        Ok(self.loader.ec_point(acc.unwrap()))
+    }
+
+    /// Returns `(2 * u + 1 - 2^window_size) * self` for every `u` in
+    /// `[0, 2^window_size)`, which is the table `add_window` selects from.
+    fn signed_odd_multiples(
+        &self,
+        window_size: usize,
+    ) -> Result<Vec<EccChip::AssignedEcPoint>, crate::Error> {
+        let assigned = self.assigned()?;
+        let ecc_chip = self.loader.ecc_chip();
+        let mut ctx = self.loader.ctx_mut();
+        let doubled = ecc_chip
+            .double(&mut ctx, &assigned)
+            .map_err(chip_error("double"))?;
+        // Odd multiples are distinct from the doubled point, so incomplete
+        // addition is enough
+        let mut positive = vec![assigned];
+        for _ in 1..1 << (window_size - 1) {
+            let next = ecc_chip
+                .add(&mut ctx, positive.last().unwrap(), &doubled)
+                .map_err(chip_error("add"))?;
+            positive.push(next);
+        }
+        let negative = positive
+            .iter()
+            .rev()
+            .map(|point| ecc_chip.neg(&mut ctx, point))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(chip_error("neg"))?;
+        Ok(negative.into_iter().chain(positive).collect())
+    }
+
+    /// Multiplies the point by the curve's cofactor to clear any small
+    /// subgroup component, which is a no-op for prime order curves.
+    pub fn mul_by_cofactor(&self) -> Self {
+        if EccChip::COFACTOR == 1 {
+            return self.clone();
+        }
+        self.mul_by_small_constant(EccChip::COFACTOR)
+    }
+
+    pub(crate) fn mul_by_small_constant(&self, k: u64) -> Self {
+        if self.is_identity_const() {
+            return self.clone();
+        }
+
+        let assigned = self.assigned().unwrap();
+        let output = self
+            .loader
+            .constrain_if_gated(&[], &[self.index], || {
+                self.loader.ecc_chip().mul_by_small_constant(
+                    &mut self.loader.ctx_mut(),
+                    &assigned,
+                    k,
+                )
+            })
+            .unwrap();
+        self.loader.ec_point(output)
+    }
+
+    pub fn neg(&self) -> Self {
+        if self.is_identity_const() {
+            return self.clone();
+        }
+
+        let assigned = self.assigned_unnormalized().unwrap();
+        let negated = self
+            .loader
+            .constrain_if_gated(&[], &[self.index], || {
+                self.loader
+                    .ecc_chip()
+                    .neg(&mut self.loader.ctx_mut(), &assigned)
+            })
+            .unwrap();
+        self.loader
+            .ec_point_with_normalized(Some(negated), self.is_normalized())
+    }
+
+    /// Negates the point in place when it isn't shared with any clone,
+    /// otherwise replaces it with a freshly negated point.
+    pub fn negate_in_place(&mut self) {
+        if self.is_identity_const() {
+            return;
+        }
+
+        if Rc::strong_count(&self.assigned) == 1 {
+            let mut assigned = self.assigned.borrow_mut();
+            self.loader
+                .ecc_chip()
+                .neg_assign(&mut self.loader.ctx_mut(), assigned.as_mut().unwrap())
+                .unwrap();
+        } else {
+            *self = self.neg();
+        }
+    }
+
+    /// Absorbs the point into `transcript` in the canonical layout of
+    /// [`ec_point_to_native`](crate::util::transcript::ec_point_to_native), which is the x-coordinate followed by the
+    /// y-coordinate, each cast into the scalar field.
+    pub fn absorb_into<T>(&self, transcript: &mut T) -> Result<(), crate::Error>
+    where
+        T: Transcript<C, Rc<Halo2Loader<'a, C, EccChip>>>,
+    {
+        transcript.common_scalar(&self.x()?)?;
+        transcript.common_scalar(&self.y()?)
+    }
+
+    /// Returns true only when both points are known to be non-identity with
+    /// different x-coordinates, which is when they are distinct cached
+    /// constants, so incomplete addition can't hit its exceptional cases.
+    pub fn can_add_incompletely(&self, other: &Self) -> bool {
+        if self.index == other.index || self.is_identity_const() || other.is_identity_const() {
+            return false;
+        }
+        match (self.constant_coordinates(), other.constant_coordinates()) {
+            (Some((lhs_x, _)), Some((rhs_x, _))) => lhs_x != rhs_x,
+            _ => false,
+        }
+    }
+
+    fn constant_coordinates(&self) -> Option<(C::Base, C::Base)> {
+        self.loader
+            .const_ec_point_coordinates
+            .borrow()
+            .get(&self.index)
+            .copied()
+    }
+
+    /// Constrains the point not to be the identity, e.g. to reject a
+    /// commitment the prover sent as the point at infinity.
+    pub fn assert_not_identity(&self, annotation: &str) -> Result<(), crate::Error> {
+        let assigned = match self.assigned.borrow().as_ref() {
+            Some(assigned) => assigned.clone(),
+            None => return Err(crate::Error::AssertionFailure(annotation.to_string())),
+        };
+        self.loader.mark_constrained(&[], &[self.index]);
+        self.loader
+            .ecc_chip()
+            .assert_not_identity(&mut self.loader.ctx_mut(), &assigned)
+            .map_err(|_| crate::Error::AssertionFailure(annotation.to_string()))
+    }
+
+    pub(crate) fn is_identity_const(&self) -> bool {
+        self.assigned.borrow().is_none()
+    }
+
+    pub fn is_normalized(&self) -> bool {
+        self.normalized.get()
+    }
+
+    pub fn normalize_if_needed(&self) {
+        if self.normalized.get() {
+            return;
+        }
+
+        let assigned = self.assigned_unnormalized().unwrap();
+        let normalized = self
+            .loader
+            .ecc_chip()
+            .normalize(&mut self.loader.ctx_mut(), &assigned)
+            .unwrap();
+        *self.assigned.borrow_mut() = Some(normalized);
+        self.normalized.set(true);
+    }
+
+    /// Returns the assigned point, assigning the identity through the chip
+    /// when it's the identity, which fails for chips that can't represent it.
+    fn assigned_unnormalized(&self) -> Result<EccChip::AssignedEcPoint, crate::Error> {
+        match self.assigned.borrow().as_ref() {
+            Some(assigned) => Ok(assigned.clone()),
+            None => self
+                .loader
+                .ecc_chip()
+                .assign_identity(&mut self.loader.ctx_mut())
+                .map_err(chip_error("assign_identity")),
+        }
     }
 }
 
@@ -466,6 +2676,16 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> PartialEq for EcPoint<
     }
 }
 
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Eq for EcPoint<'a, C, EccChip> {}
+
+/// Hashes only the index, so it reflects identity of the loaded element
+/// rather than its value, consistent with `PartialEq`.
+impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Hash for EcPoint<'a, C, EccChip> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
 impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> LoadedEcPoint<C>
     for EcPoint<'a, C, EccChip>
 {
@@ -479,52 +2699,11 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> LoadedEcPoint<C>
         pairs: impl IntoIterator<Item = (Scalar<'a, C, EccChip>, Self)>,
     ) -> Self {
         let pairs = pairs.into_iter().collect_vec();
-        let loader = &pairs[0].0.loader;
-
-        let (non_scaled, scaled) = pairs.iter().fold(
-            (Vec::new(), Vec::new()),
-            |(mut non_scaled, mut scaled), (scalar, ec_point)| {
-                if matches!(scalar.value, Value::Constant(constant) if constant == C::Scalar::one())
-                {
-                    non_scaled.push(ec_point.assigned());
-                } else {
-                    scaled.push((ec_point.assigned(), scalar.assigned()))
-                }
-                (non_scaled, scaled)
-            },
-        );
-
-        let output = iter::empty()
-            .chain(if scaled.is_empty() {
-                None
-            } else {
-                Some(
-                    loader
-                        .ecc_chip
-                        .borrow_mut()
-                        .multi_scalar_multiplication(&mut loader.ctx_mut(), scaled)
-                        .unwrap(),
-                )
-            })
-            .chain(non_scaled)
-            .reduce(|acc, ec_point| {
-                EccInstructions::add(
-                    loader.ecc_chip().deref(),
-                    &mut loader.ctx_mut(),
-                    &acc,
-                    &ec_point,
-                )
-                .unwrap()
-            })
-            .map(|output| {
-                loader
-                    .ecc_chip()
-                    .normalize(&mut loader.ctx_mut(), &output)
-                    .unwrap()
-            })
-            .unwrap();
-
-        loader.ec_point(output)
+        let loader = pairs
+            .first()
+            .map(|(scalar, _)| scalar.loader.clone())
+            .expect("Empty msm has no loader, use Halo2Loader::try_multi_scalar_multiplication");
+        loader.try_multi_scalar_multiplication(pairs).unwrap()
     }
 }
 
@@ -533,6 +2712,7 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> Debug for EcPoint<'a,
         f.debug_struct("EcPoint")
             .field("index", &self.index)
             .field("assigned", &self.assigned)
+            .field("normalized", &self.normalized)
             .finish()
     }
 }
@@ -543,7 +2723,13 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> ScalarLoader<C::Scalar
     type LoadedScalar = Scalar<'a, C, EccChip>;
 
     fn load_const(&self, value: &C::Scalar) -> Scalar<'a, C, EccChip> {
-        self.scalar(Value::Constant(*value))
+        let output = self.scalar(Value::Constant(*value));
+        #[cfg(feature = "op_trace")]
+        self.record(Op::LoadConst {
+            output: output.index,
+            value: *value,
+        });
+        output
     }
 
     fn assert_eq(
@@ -552,9 +2738,191 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> ScalarLoader<C::Scalar
         lhs: &Scalar<'a, C, EccChip>,
         rhs: &Scalar<'a, C, EccChip>,
     ) -> Result<(), crate::Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("assert_eq", annotation, lhs = lhs.index, rhs = rhs.index)
+            .entered();
+        #[cfg(feature = "op_trace")]
+        self.record(Op::AssertEq {
+            lhs: lhs.index,
+            rhs: rhs.index,
+        });
+
+        self.mark_constrained(&[lhs.index, rhs.index], &[]);
+        // Reduced first so equal values in different limb layouts compare equal
+        let (lhs, rhs) = (lhs.assigned(), rhs.assigned());
+        let (lhs, rhs) = {
+            let scalar_chip = self.scalar_chip();
+            let mut ctx = self.ctx_mut();
+            (
+                scalar_chip.reduce(&mut ctx, &lhs).unwrap(),
+                scalar_chip.reduce(&mut ctx, &rhs).unwrap(),
+            )
+        };
+        let result = self
+            .scalar_chip()
+            .assert_equal(&mut self.ctx_mut(), &lhs, &rhs)
+            .map_err(|_| self.scalar_assertion_failure(annotation, &lhs, &rhs));
+        self.breadcrumb(annotation, result)
+    }
+
+    fn assert_zero(
+        &self,
+        annotation: &str,
+        x: &Scalar<'a, C, EccChip>,
+    ) -> Result<(), crate::Error> {
+        self.mark_constrained(&[x.index], &[]);
+        let result = match &x.value {
+            Value::Constant(constant) => (*constant == C::Scalar::zero())
+                .then_some(())
+                .ok_or_else(|| crate::Error::AssertionFailure(annotation.to_string())),
+            Value::Assigned(assigned) => self
+                .scalar_chip()
+                .assert_zero(&mut self.ctx_mut(), assigned)
+                .map_err(|_| crate::Error::AssertionFailure(annotation.to_string())),
+        };
+        self.breadcrumb(annotation, result)
+    }
+
+    fn assert_one(&self, annotation: &str, x: &Scalar<'a, C, EccChip>) -> Result<(), crate::Error> {
+        self.mark_constrained(&[x.index], &[]);
+        let result = match &x.value {
+            Value::Constant(constant) => (*constant == C::Scalar::one())
+                .then_some(())
+                .ok_or_else(|| crate::Error::AssertionFailure(annotation.to_string())),
+            Value::Assigned(assigned) => self
+                .scalar_chip()
+                .assert_one(&mut self.ctx_mut(), assigned)
+                .map_err(|_| crate::Error::AssertionFailure(annotation.to_string())),
+        };
+        self.breadcrumb(annotation, result)
+    }
+
+    fn assert_non_zero(
+        &self,
+        annotation: &str,
+        x: &Scalar<'a, C, EccChip>,
+    ) -> Result<(), crate::Error> {
+        let mut is_zero = false;
+        x.to_native().map(|x| is_zero = x == C::Scalar::zero());
+        if is_zero {
+            return Err(crate::Error::AssertionFailure(annotation.to_string()));
+        }
+        match &x.value {
+            Value::Constant(_) => Ok(()),
+            // Inversion constrains `x * x_inv == 1`
+            Value::Assigned(_) => self.try_invert(x).map(|_| ()),
+        }
+    }
+
+    fn conditional_assert_eq(
+        &self,
+        annotation: &str,
+        cond: &Scalar<'a, C, EccChip>,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Result<(), crate::Error> {
+        match cond.value {
+            Value::Constant(constant) if constant == C::Scalar::zero() => Ok(()),
+            Value::Constant(constant) if constant == C::Scalar::one() => {
+                self.assert_eq(annotation, lhs, rhs)
+            }
+            _ => {
+                let diff = self.sum_products_with_coeff_and_const(
+                    &[
+                        (C::Scalar::one(), cond, lhs),
+                        (-C::Scalar::one(), cond, rhs),
+                    ],
+                    C::Scalar::zero(),
+                );
+                self.assert_eq(annotation, &diff, &self.load_zero())
+            }
+        }
+    }
+
+    fn conditional_negate(
+        &self,
+        x: &Scalar<'a, C, EccChip>,
+        bit: &Scalar<'a, C, EccChip>,
+    ) -> Result<Scalar<'a, C, EccChip>, crate::Error> {
+        match bit.value {
+            Value::Constant(constant) if constant == C::Scalar::zero() => Ok(x.clone()),
+            Value::Constant(constant) if constant == C::Scalar::one() => self.try_neg(x),
+            Value::Constant(_) => Err(crate::Error::AssertionFailure(
+                "bit is not boolean".to_string(),
+            )),
+            Value::Assigned(ref assigned) => {
+                self.scalar_chip()
+                    .assert_bit(&mut self.ctx_mut(), assigned)
+                    .map_err(chip_error("conditional_negate"))?;
+                self.mark_constrained(&[bit.index], &[]);
+                // `x` added as a linear term, as a product with one would
+                // assign a constant cell for it
+                let product = self.sum_products_with_coeff_and_const(
+                    &[(-C::Scalar::from(2), bit, x)],
+                    C::Scalar::zero(),
+                );
+                Ok(self.sum_with_coeff(&[(C::Scalar::one(), x), (C::Scalar::one(), &product)]))
+            }
+        }
+    }
+
+    fn is_equal(
+        &self,
+        lhs: &Scalar<'a, C, EccChip>,
+        rhs: &Scalar<'a, C, EccChip>,
+    ) -> Scalar<'a, C, EccChip> {
+        if let (Value::Constant(lhs), Value::Constant(rhs)) = (&lhs.value, &rhs.value) {
+            return self.load_const(&if lhs == rhs {
+                C::Scalar::one()
+            } else {
+                C::Scalar::zero()
+            });
+        }
+
+        self.mark_constrained(&[lhs.index, rhs.index], &[]);
+        let (lhs, rhs) = (lhs.assigned(), rhs.assigned());
+        let is_equal = self
+            .scalar_chip()
+            .is_equal(&mut self.ctx_mut(), &lhs, &rhs)
+            .unwrap();
+        self.scalar(Value::Assigned(is_equal))
+    }
+
+    fn to_bits(
+        &self,
+        x: &Scalar<'a, C, EccChip>,
+        num_bits: usize,
+    ) -> Result<Vec<Scalar<'a, C, EccChip>>, crate::Error> {
+        match &x.value {
+            Value::Constant(constant) => Ok(NativeLoader
+                .to_bits(constant, num_bits)?
+                .iter()
+                .map(|bit| self.load_const(bit))
+                .collect()),
+            Value::Assigned(assigned) => {
+                self.mark_constrained(&[x.index], &[]);
+                let bits = self
+                    .scalar_chip()
+                    .to_bits(&mut self.ctx_mut(), assigned, num_bits)
+                    .map_err(chip_error("to_bits"))?;
+                Ok(bits
+                    .into_iter()
+                    .map(|bit| self.scalar(Value::Assigned(bit)))
+                    .collect())
+            }
+        }
+    }
+
+    fn from_bytes_le(&self, bytes: &[Scalar<'a, C, EccChip>]) -> Scalar<'a, C, EccChip> {
+        let assigned = bytes.iter().map(|byte| byte.assigned()).collect_vec();
         self.scalar_chip()
-            .assert_equal(&mut self.ctx_mut(), &lhs.assigned(), &rhs.assigned())
-            .map_err(|_| crate::Error::AssertionFailure(annotation.to_string()))
+            .assert_bytes_le(&mut self.ctx_mut(), &assigned)
+            .unwrap();
+        self.mark_constrained(&bytes.iter().map(|byte| byte.index).collect_vec(), &[]);
+        let coeffs = iter::successors(Some(C::Scalar::one()), |coeff| {
+            Some(*coeff * C::Scalar::from(256))
+        });
+        self.sum_with_coeff(&coeffs.zip(bytes.iter()).collect_vec())
     }
 
     fn sum_with_coeff_and_const(
@@ -562,15 +2930,128 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> ScalarLoader<C::Scalar
         values: &[(C::Scalar, &Scalar<'a, C, EccChip>)],
         constant: C::Scalar,
     ) -> Scalar<'a, C, EccChip> {
-        let values = values
+        // Fold constant terms so only the assigned ones reach the chip
+        let (constant, values) = values.iter().fold(
+            (constant, Vec::with_capacity(values.len())),
+            |(constant, mut values), (coeff, value)| match &value.value {
+                Value::Constant(value) => (constant + *coeff * value, values),
+                Value::Assigned(_) => {
+                    values.push((*coeff, *value));
+                    (constant, values)
+                }
+            },
+        );
+        match values.as_slice() {
+            [] => return self.load_const(&constant),
+            [(coeff, value)] if *coeff == C::Scalar::one() && constant == C::Scalar::zero() => {
+                return (*value).clone()
+            }
+            _ => {}
+        }
+
+        let max_sum_terms = self.scalar_limb_info().max_sum_terms(C::Scalar::NUM_BITS);
+        let mut assigned = values
             .iter()
             .map(|(coeff, value)| (*coeff, value.assigned()))
             .collect_vec();
-        self.scalar(Value::Assigned(
-            self.scalar_chip()
-                .sum_with_coeff_and_const(&mut self.ctx_mut(), &values, constant)
-                .unwrap(),
-        ))
+        // Reduce the leading terms into an intermediate cell until the rest
+        // fits in a single call.
+        while assigned.len() > max_sum_terms {
+            let rest = assigned.split_off(max_sum_terms);
+            let reduced = self
+                .scalar_chip()
+                .sum_with_coeff_and_const(&mut self.ctx_mut(), &assigned, C::Scalar::zero())
+                .unwrap();
+            assigned = iter::once((C::Scalar::one(), reduced))
+                .chain(rest)
+                .collect();
+        }
+        let output = self
+            .scalar_chip()
+            .sum_with_coeff_and_const(&mut self.ctx_mut(), &assigned, constant)
+            .unwrap();
+        self.mark_constrained(
+            &values.iter().map(|(_, value)| value.index).collect_vec(),
+            &[],
+        );
+        let output = self.scalar(Value::Assigned(output));
+        #[cfg(feature = "op_trace")]
+        self.record(Op::SumWithCoeffAndConst {
+            output: output.index,
+            values: values
+                .iter()
+                .map(|(coeff, value)| (*coeff, value.index))
+                .collect(),
+            constant,
+        });
+        output
+    }
+
+    fn scale(&self, c: C::Scalar, xs: &[Scalar<'a, C, EccChip>]) -> Vec<Scalar<'a, C, EccChip>> {
+        if c == C::Scalar::zero() {
+            return xs.iter().map(|_| self.load_zero()).collect();
+        }
+        if c == C::Scalar::one() {
+            return xs.to_vec();
+        }
+
+        let values = {
+            let scalar_chip = self.scalar_chip();
+            let mut ctx = self.ctx_mut();
+            xs.iter()
+                .map(|x| match &x.value {
+                    Value::Constant(constant) => Value::Constant(c * constant),
+                    Value::Assigned(assigned) => Value::Assigned(
+                        scalar_chip
+                            .sum_with_coeff_and_const(
+                                &mut ctx,
+                                &[(c, assigned.clone())],
+                                C::Scalar::zero(),
+                            )
+                            .unwrap(),
+                    ),
+                })
+                .collect_vec()
+        };
+        self.mark_constrained(&xs.iter().map(|x| x.index).collect_vec(), &[]);
+        xs.iter()
+            .zip(values)
+            .map(|(_x, value)| {
+                let output = self.scalar(value);
+                #[cfg(feature = "op_trace")]
+                self.record(Op::SumWithCoeffAndConst {
+                    output: output.index,
+                    values: vec![(c, _x.index)],
+                    constant: C::Scalar::zero(),
+                });
+                output
+            })
+            .collect()
+    }
+
+    fn product(&self, values: &[&Scalar<'a, C, EccChip>]) -> Scalar<'a, C, EccChip> {
+        // Fold constant factors into a single coefficient of the assigned ones
+        let (constant, values) = values.iter().fold(
+            (C::Scalar::one(), Vec::with_capacity(values.len())),
+            |(constant, mut values), value| match &value.value {
+                Value::Constant(value) => (constant * value, values),
+                Value::Assigned(_) => {
+                    values.push(*value);
+                    (constant, values)
+                }
+            },
+        );
+        if constant == C::Scalar::zero() {
+            return self.load_zero();
+        }
+        match values.as_slice() {
+            [] => self.load_const(&constant),
+            [value] => self.mul(&self.load_const(&constant), value),
+            [lhs, rhs, rest @ ..] => rest.iter().fold(
+                self.sum_products_with_coeff(&[(constant, lhs, rhs)]),
+                |acc, value| acc * *value,
+            ),
+        }
     }
 
     fn sum_products_with_coeff_and_const(
@@ -578,15 +3059,43 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> ScalarLoader<C::Scalar
         values: &[(C::Scalar, &Scalar<'a, C, EccChip>, &Scalar<'a, C, EccChip>)],
         constant: C::Scalar,
     ) -> Scalar<'a, C, EccChip> {
-        let values = values
+        if values.is_empty() {
+            return self.load_const(&constant);
+        }
+
+        let mut assigned = BTreeMap::new();
+        let mut assign = |scalar: &Scalar<'a, C, EccChip>| {
+            assigned
+                .entry(scalar.index)
+                .or_insert_with(|| scalar.assigned())
+                .clone()
+        };
+        let assigned = values
             .iter()
-            .map(|(coeff, lhs, rhs)| (*coeff, lhs.assigned(), rhs.assigned()))
+            .map(|(coeff, lhs, rhs)| (*coeff, assign(lhs), assign(rhs)))
             .collect_vec();
-        self.scalar(Value::Assigned(
-            self.scalar_chip()
-                .sum_products_with_coeff_and_const(&mut self.ctx_mut(), &values, constant)
-                .unwrap(),
-        ))
+        let output = self
+            .scalar_chip()
+            .sum_products_with_coeff_and_const(&mut self.ctx_mut(), &assigned, constant)
+            .unwrap();
+        self.mark_constrained(
+            &values
+                .iter()
+                .flat_map(|(_, lhs, rhs)| [lhs.index, rhs.index])
+                .collect_vec(),
+            &[],
+        );
+        let output = self.scalar(Value::Assigned(output));
+        #[cfg(feature = "op_trace")]
+        self.record(Op::SumProductsWithCoeffAndConst {
+            output: output.index,
+            values: values
+                .iter()
+                .map(|(coeff, lhs, rhs)| (*coeff, lhs.index, rhs.index))
+                .collect(),
+            constant,
+        });
+        output
     }
 }
 
@@ -596,7 +3105,66 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> EcPointLoader<C>
     type LoadedEcPoint = EcPoint<'a, C, EccChip>;
 
     fn ec_point_load_const(&self, ec_point: &C) -> EcPoint<'a, C, EccChip> {
-        self.assign_const_ec_point(*ec_point)
+        let output = self.assign_const_ec_point(*ec_point);
+        #[cfg(feature = "op_trace")]
+        self.record(Op::LoadConstEcPoint {
+            output: output.index,
+            value: *ec_point,
+        });
+        output
+    }
+
+    /// Scales points by their constant coefficients with the chip's
+    /// `mul_const` up front when it has one, so the `msm` only adds them up.
+    /// Otherwise the coefficients go into the `msm` as constant scalars.
+    fn linear_combination(
+        &self,
+        terms: &[(C::Scalar, &EcPoint<'a, C, EccChip>)],
+    ) -> Result<EcPoint<'a, C, EccChip>, crate::Error> {
+        let one = self.load_one();
+        let supports_mul_const = self.ecc_chip().supports_mul_const();
+        let mut pairs = Vec::with_capacity(terms.len());
+        for &(coeff, ec_point) in terms {
+            if coeff == C::Scalar::zero() || ec_point.is_identity_const() {
+                continue;
+            }
+            if coeff == C::Scalar::one() {
+                pairs.push((one.clone(), ec_point.clone()));
+                continue;
+            }
+            if !supports_mul_const {
+                pairs.push((self.load_const(&coeff), ec_point.clone()));
+                continue;
+            }
+            let assigned = ec_point.assigned()?;
+            let scaled = self
+                .ecc_chip()
+                .mul_const(&mut self.ctx_mut(), &assigned, coeff)
+                .map_err(chip_error("mul_const"))?;
+            self.mark_constrained(&[], &[ec_point.index]);
+            pairs.push((one.clone(), self.ec_point(scaled)));
+        }
+        self.try_multi_scalar_multiplication(pairs)
+    }
+
+    fn points_equal(
+        &self,
+        lhs: &EcPoint<'a, C, EccChip>,
+        rhs: &EcPoint<'a, C, EccChip>,
+    ) -> Scalar<'a, C, EccChip> {
+        match (lhs.is_identity_const(), rhs.is_identity_const()) {
+            (true, true) => return self.load_one(),
+            (true, false) => return rhs.is_identity(),
+            (false, true) => return lhs.is_identity(),
+            (false, false) => {}
+        }
+        self.mark_constrained(&[], &[lhs.index, rhs.index]);
+        let (lhs, rhs) = (lhs.assigned().unwrap(), rhs.assigned().unwrap());
+        let is_equal = self
+            .ecc_chip()
+            .is_equal(&mut self.ctx_mut(), &lhs, &rhs)
+            .unwrap();
+        self.scalar(Value::Assigned(is_equal))
     }
 
     fn ec_point_assert_eq(
@@ -605,9 +3173,33 @@ impl<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> EcPointLoader<C>
         lhs: &EcPoint<'a, C, EccChip>,
         rhs: &EcPoint<'a, C, EccChip>,
     ) -> Result<(), crate::Error> {
-        self.ecc_chip()
-            .assert_equal(&mut self.ctx_mut(), &lhs.assigned(), &rhs.assigned())
-            .map_err(|_| crate::Error::AssertionFailure(annotation.to_string()))
+        #[cfg(feature = "op_trace")]
+        self.record(Op::EcPointAssertEq {
+            lhs: lhs.index,
+            rhs: rhs.index,
+        });
+        let result = match (lhs.is_identity_const(), rhs.is_identity_const()) {
+            (true, true) => Ok(()),
+            (false, false) => {
+                self.mark_constrained(&[], &[lhs.index, rhs.index]);
+                let (lhs, rhs) = (lhs.assigned()?, rhs.assigned()?);
+                self.ecc_chip()
+                    .assert_equal(&mut self.ctx_mut(), &lhs, &rhs)
+                    .map_err(|_| crate::Error::AssertionFailure(annotation.to_string()))
+            }
+            _ => Err(crate::Error::AssertionFailure(annotation.to_string())),
+        };
+        self.breadcrumb(annotation, result)
+    }
+
+    fn assert_eq_many(
+        &self,
+        annotation: &str,
+        lhs: &[EcPoint<'a, C, EccChip>],
+        rhs: &[EcPoint<'a, C, EccChip>],
+    ) -> Result<(), crate::Error> {
+        let result = self.try_assert_eq_many(annotation, lhs, rhs);
+        self.breadcrumb(annotation, result)
     }
 }
 
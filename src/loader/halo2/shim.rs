@@ -1,6 +1,6 @@
 use crate::util::arithmetic::{CurveAffine, FieldExt};
 use halo2_proofs::{
-    circuit::{Cell, Value},
+    circuit::{AssignedCell, Cell, Layouter, Value},
     plonk::Error,
 };
 use std::fmt::Debug;
@@ -11,6 +11,49 @@ pub trait Context: Debug {
     fn offset(&self) -> usize;
 }
 
+/// How an integer is split into limbs by an [`IntegerInstructions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LimbInfo {
+    pub num_limbs: usize,
+    pub bits_per_limb: usize,
+}
+
+impl LimbInfo {
+    /// Returns how many terms can be summed before a reduction without the
+    /// limbs overflowing a native field of `native_bits`, which is unbounded
+    /// when integers are native.
+    pub fn max_sum_terms(&self, native_bits: u32) -> usize {
+        if self.num_limbs == 1 {
+            return usize::MAX;
+        }
+        let headroom = (native_bits as usize).saturating_sub(self.bits_per_limb + 1);
+        1usize
+            .checked_shl(headroom as u32)
+            .unwrap_or(usize::MAX)
+            .max(2)
+    }
+}
+
+/// Where an assignment sits within a batch made by the loader, so chips that
+/// pack several integers into one row know which of them may share it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PackingHint {
+    /// Number of assignments of the batch made right before this one.
+    pub preceding: usize,
+    /// Number of assignments of the batch made right after this one.
+    pub following: usize,
+}
+
+impl PackingHint {
+    /// Returns the hints for a batch of `len` consecutive assignments.
+    pub fn batch(len: usize) -> impl Iterator<Item = Self> {
+        (0..len).map(move |idx| Self {
+            preceding: idx,
+            following: len - idx - 1,
+        })
+    }
+}
+
 pub trait IntegerInstructions<'a, F: FieldExt>: Clone + Debug {
     type Context: Context;
     type Integer: Clone + Debug;
@@ -18,18 +61,80 @@ pub trait IntegerInstructions<'a, F: FieldExt>: Clone + Debug {
 
     fn integer(&self, fe: F) -> Self::Integer;
 
+    fn value_of(&self, integer: &Self::AssignedInteger) -> Value<F>;
+
+    fn limbs(&self, integer: &Self::AssignedInteger) -> Vec<Value<F>>;
+
+    fn limb_info(&self) -> LimbInfo;
+
+    /// Composes an integer from already assigned `limbs`, least significant
+    /// first, range checking them as the chip requires. Chips that can't do so
+    /// refuse to.
+    fn from_limbs(
+        &self,
+        _: &mut Self::Context,
+        _: Vec<AssignedCell<F, F>>,
+    ) -> Result<Self::AssignedInteger, Error> {
+        Err(Error::Synthesis)
+    }
+
+    /// Returns the cell holding `integer` when it's a single native cell,
+    /// otherwise `None`.
+    fn native_cell(&self, _: &Self::AssignedInteger) -> Option<AssignedCell<F, F>> {
+        None
+    }
+
     fn assign_integer(
         &self,
         ctx: &mut Self::Context,
         integer: Value<Self::Integer>,
     ) -> Result<Self::AssignedInteger, Error>;
 
+    /// Assigns `integer` as part of a batch described by `hint`, so chips can
+    /// pack it into a row shared with its neighbours. Chips without packing
+    /// assign it on its own.
+    fn assign_integer_packed(
+        &self,
+        ctx: &mut Self::Context,
+        integer: Value<Self::Integer>,
+        _: PackingHint,
+    ) -> Result<Self::AssignedInteger, Error> {
+        self.assign_integer(ctx, integer)
+    }
+
     fn assign_constant(
         &self,
         ctx: &mut Self::Context,
         integer: F,
     ) -> Result<Self::AssignedInteger, Error>;
 
+    /// Constrains `integer` to equal the cell at `row` of the instance
+    /// column. It takes a layouter since instance cells can't be constrained
+    /// from within a region.
+    fn expose_public(
+        &self,
+        layouter: impl Layouter<F>,
+        integer: Self::AssignedInteger,
+        row: usize,
+    ) -> Result<(), Error>;
+
+    /// Assigns `a` again in a fresh cell constrained to be equal to it.
+    fn copy(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedInteger,
+    ) -> Result<Self::AssignedInteger, Error>;
+
+    /// Returns `a` in its canonical limb representation. Chips whose integers
+    /// are always canonical, such as native ones, return it as is.
+    fn reduce(
+        &self,
+        _: &mut Self::Context,
+        a: &Self::AssignedInteger,
+    ) -> Result<Self::AssignedInteger, Error> {
+        Ok(a.clone())
+    }
+
     fn sum_with_coeff_and_const(
         &self,
         ctx: &mut Self::Context,
@@ -44,6 +149,14 @@ pub trait IntegerInstructions<'a, F: FieldExt>: Clone + Debug {
         constant: F::Scalar,
     ) -> Result<Self::AssignedInteger, Error>;
 
+    fn mul_add(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedInteger,
+        b: &Self::AssignedInteger,
+        c: &Self::AssignedInteger,
+    ) -> Result<Self::AssignedInteger, Error>;
+
     fn sub(
         &self,
         ctx: &mut Self::Context,
@@ -63,12 +176,80 @@ pub trait IntegerInstructions<'a, F: FieldExt>: Clone + Debug {
         a: &Self::AssignedInteger,
     ) -> Result<Self::AssignedInteger, Error>;
 
+    /// Returns `a / b`, failing when `b` is known to be zero.
+    fn div(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedInteger,
+        b: &Self::AssignedInteger,
+    ) -> Result<Self::AssignedInteger, Error>;
+
+    /// Constrains `a_inv` to be the inverse of `a`, where the witness is
+    /// computed beforehand by the caller.
+    fn invert_with_witness(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedInteger,
+        a_inv: Value<F>,
+    ) -> Result<Self::AssignedInteger, Error>;
+
+    /// Constrains `sum` to be `a + b`, where the witness is computed
+    /// beforehand by the caller.
+    fn add_with_witness(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedInteger,
+        b: &Self::AssignedInteger,
+        sum: Value<F>,
+    ) -> Result<Self::AssignedInteger, Error>;
+
+    /// Constrains `product` to be `a * b`, where the witness is computed
+    /// beforehand by the caller.
+    fn mul_with_witness(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedInteger,
+        b: &Self::AssignedInteger,
+        product: Value<F>,
+    ) -> Result<Self::AssignedInteger, Error>;
+
     fn assert_equal(
         &self,
         ctx: &mut Self::Context,
         a: &Self::AssignedInteger,
         b: &Self::AssignedInteger,
     ) -> Result<(), Error>;
+
+    fn assert_zero(&self, ctx: &mut Self::Context, a: &Self::AssignedInteger) -> Result<(), Error>;
+
+    fn assert_one(&self, ctx: &mut Self::Context, a: &Self::AssignedInteger) -> Result<(), Error>;
+
+    fn assert_bit(&self, ctx: &mut Self::Context, a: &Self::AssignedInteger) -> Result<(), Error>;
+
+    /// Returns a bit that is one when `a` equals `b`.
+    fn is_equal(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedInteger,
+        b: &Self::AssignedInteger,
+    ) -> Result<Self::AssignedInteger, Error>;
+
+    /// Returns the little-endian bits of `a`, constrained to be boolean and to
+    /// compose back to `a`.
+    fn to_bits(
+        &self,
+        ctx: &mut Self::Context,
+        a: &Self::AssignedInteger,
+        num_bits: usize,
+    ) -> Result<Vec<Self::AssignedInteger>, Error>;
+
+    /// Constrains each of `bytes` to be in `[0, 256)` and their little-endian
+    /// composition to be less than the modulus.
+    fn assert_bytes_le(
+        &self,
+        ctx: &mut Self::Context,
+        bytes: &[Self::AssignedInteger],
+    ) -> Result<(), Error>;
 }
 
 pub trait EccInstructions<'a, C: CurveAffine>: Clone + Debug {
@@ -83,9 +264,19 @@ pub trait EccInstructions<'a, C: CurveAffine>: Clone + Debug {
     type AssignedEcPoint: Clone + Debug;
     type Scalar: Clone + Debug;
     type AssignedScalar: Clone + Debug;
+    type AssignedBase: Clone + Debug;
+
+    /// Cofactor of the curve, which is one for prime order curves.
+    const COFACTOR: u64 = 1;
 
     fn scalar_chip(&self) -> &Self::ScalarChip;
 
+    /// Checks the chip's parameters are consistent with the curve, so a
+    /// misconfigured chip is reported before any synthesis happens.
+    fn validate_params(&self) -> Result<(), String> {
+        Ok(())
+    }
+
     fn assign_constant(
         &self,
         ctx: &mut Self::Context,
@@ -98,6 +289,64 @@ pub trait EccInstructions<'a, C: CurveAffine>: Clone + Debug {
         point: Value<C>,
     ) -> Result<Self::AssignedEcPoint, Error>;
 
+    /// Assigns `points` in one go, so chips can lay them out contiguously.
+    fn assign_points(
+        &self,
+        ctx: &mut Self::Context,
+        points: Vec<Value<C>>,
+    ) -> Result<Vec<Self::AssignedEcPoint>, Error> {
+        points
+            .into_iter()
+            .map(|point| self.assign_point(ctx, point))
+            .collect()
+    }
+
+    fn assign_identity(&self, _: &mut Self::Context) -> Result<Self::AssignedEcPoint, Error> {
+        Err(Error::Synthesis)
+    }
+
+    fn is_identity(
+        &self,
+        ctx: &mut Self::Context,
+        point: &Self::AssignedEcPoint,
+    ) -> Result<Self::AssignedScalar, Error>;
+
+    /// Constrains `point` not to be the identity, by its `is_identity` bit
+    /// being zero unless the chip has a cheaper way to do so.
+    fn assert_not_identity(
+        &self,
+        ctx: &mut Self::Context,
+        point: &Self::AssignedEcPoint,
+    ) -> Result<(), Error> {
+        let is_identity = self.is_identity(ctx, point)?;
+        self.scalar_chip().assert_zero(ctx, &is_identity)
+    }
+
+    /// Returns the x-coordinate of a normalized `point` cast into the scalar
+    /// field, constrained to be consistent with the point's representation.
+    fn x_coordinate(
+        &self,
+        ctx: &mut Self::Context,
+        point: &Self::AssignedEcPoint,
+    ) -> Result<Self::AssignedScalar, Error>;
+
+    /// Returns the y-coordinate of a normalized `point` cast into the scalar
+    /// field, constrained to be consistent with the point's representation.
+    fn y_coordinate(
+        &self,
+        ctx: &mut Self::Context,
+        point: &Self::AssignedEcPoint,
+    ) -> Result<Self::AssignedScalar, Error>;
+
+    /// Casts `scalar` into the base field, where the caller is expected to
+    /// have checked its value fits in. The cast is only constrained modulo
+    /// the scalar field modulus.
+    fn scalar_to_base(
+        &self,
+        ctx: &mut Self::Context,
+        scalar: &Self::AssignedScalar,
+    ) -> Result<Self::AssignedBase, Error>;
+
     fn add(
         &self,
         ctx: &mut Self::Context,
@@ -105,6 +354,139 @@ pub trait EccInstructions<'a, C: CurveAffine>: Clone + Debug {
         p1: &Self::AssignedEcPoint,
     ) -> Result<Self::AssignedEcPoint, Error>;
 
+    /// Adds two points that might not be normalized, returning a point that
+    /// might not be normalized either. Chips without a dedicated
+    /// representation for this fall back to `add`.
+    fn add_projective(
+        &self,
+        ctx: &mut Self::Context,
+        p0: &Self::AssignedEcPoint,
+        p1: &Self::AssignedEcPoint,
+    ) -> Result<Self::AssignedEcPoint, Error> {
+        self.add(ctx, p0, p1)
+    }
+
+    /// Adds two points without assuming they are distinct, which costs more
+    /// rows than `add`. Chips without complete formulas refuse to do so.
+    fn add_complete(
+        &self,
+        _: &mut Self::Context,
+        _: &Self::AssignedEcPoint,
+        _: &Self::AssignedEcPoint,
+    ) -> Result<Self::AssignedEcPoint, Error> {
+        Err(Error::Synthesis)
+    }
+
+    fn neg(
+        &self,
+        ctx: &mut Self::Context,
+        point: &Self::AssignedEcPoint,
+    ) -> Result<Self::AssignedEcPoint, Error>;
+
+    /// Returns a bit that is one when `p0` equals `p1`.
+    fn is_equal(
+        &self,
+        ctx: &mut Self::Context,
+        p0: &Self::AssignedEcPoint,
+        p1: &Self::AssignedEcPoint,
+    ) -> Result<Self::AssignedScalar, Error>;
+
+    /// Negates `point` in place. Chips without a cheaper way to do so fall
+    /// back to `neg`.
+    fn neg_assign(
+        &self,
+        ctx: &mut Self::Context,
+        point: &mut Self::AssignedEcPoint,
+    ) -> Result<(), Error> {
+        *point = self.neg(ctx, point)?;
+        Ok(())
+    }
+
+    /// Returns `point` doubled. Chips without a dedicated routine for this
+    /// fall back to `add_complete`.
+    fn double(
+        &self,
+        ctx: &mut Self::Context,
+        point: &Self::AssignedEcPoint,
+    ) -> Result<Self::AssignedEcPoint, Error> {
+        self.add_complete(ctx, point, point)
+    }
+
+    /// Returns whether the chip implements `add_window`.
+    fn supports_add_window(&self) -> bool {
+        false
+    }
+
+    /// Doubles `acc` once per bit and adds `table[idx]` to it, where `idx` is
+    /// composed of the little-endian `bits`, which is a single window of the
+    /// loader's signed window multiplication. Without `acc` it returns
+    /// `table[idx]` as is. Chips that can't select from a table don't
+    /// implement it, as reported by `supports_add_window`.
+    fn add_window(
+        &self,
+        _: &mut Self::Context,
+        _: Option<&Self::AssignedEcPoint>,
+        _: &[Self::AssignedEcPoint],
+        _: &[Self::AssignedScalar],
+    ) -> Result<Self::AssignedEcPoint, Error> {
+        Err(Error::Synthesis)
+    }
+
+    /// Returns whether the chip implements `mul_const`.
+    fn supports_mul_const(&self) -> bool {
+        false
+    }
+
+    /// Multiplies `point` by a constant `scalar`, for chips that can do so
+    /// cheaper than by an assigned scalar, as reported by
+    /// `supports_mul_const`. Callers go through `multi_scalar_multiplication`
+    /// for chips without it.
+    fn mul_const(
+        &self,
+        _: &mut Self::Context,
+        _: &Self::AssignedEcPoint,
+        _: C::Scalar,
+    ) -> Result<Self::AssignedEcPoint, Error> {
+        Err(Error::Synthesis)
+    }
+
+    /// Multiplies `point` by a small constant `k` with double-and-add on
+    /// `add_complete`, so chips without complete addition refuse to do so.
+    fn mul_by_small_constant(
+        &self,
+        ctx: &mut Self::Context,
+        point: &Self::AssignedEcPoint,
+        k: u64,
+    ) -> Result<Self::AssignedEcPoint, Error> {
+        if k == 0 {
+            return self.assign_identity(ctx);
+        }
+
+        let mut acc = point.clone();
+        for idx in (0..63 - k.leading_zeros()).rev() {
+            acc = self.add_complete(ctx, &acc, &acc)?;
+            if (k >> idx) & 1 == 1 {
+                acc = self.add_complete(ctx, &acc, point)?;
+            }
+        }
+        Ok(acc)
+    }
+
+    /// Multiplies `point` by `COFACTOR` to clear any small subgroup component.
+    fn mul_by_cofactor(
+        &self,
+        ctx: &mut Self::Context,
+        point: &Self::AssignedEcPoint,
+    ) -> Result<Self::AssignedEcPoint, Error> {
+        self.mul_by_small_constant(ctx, point, Self::COFACTOR)
+    }
+
+    /// Maximum number of terms accepted by a single
+    /// `multi_scalar_multiplication` call.
+    fn max_msm_terms(&self) -> usize {
+        usize::MAX
+    }
+
     fn multi_scalar_multiplication(
         &mut self,
         ctx: &mut Self::Context,
@@ -127,25 +509,30 @@ pub trait EccInstructions<'a, C: CurveAffine>: Clone + Debug {
 
 mod halo2_wrong {
     use crate::{
-        loader::halo2::{Context, EccInstructions, IntegerInstructions},
+        loader::halo2::{Context, EccInstructions, IntegerInstructions, LimbInfo},
         util::{
-            arithmetic::{CurveAffine, FieldExt, Group},
+            arithmetic::{
+                fe_from_big, fe_to_big, fe_to_fe, modulus, CurveAffine, Field, FieldExt, Group,
+                PrimeField,
+            },
             Itertools,
         },
     };
     use halo2_proofs::{
-        circuit::{AssignedCell, Cell, Value},
+        circuit::{AssignedCell, Cell, Layouter, Value},
         plonk::Error,
     };
     use halo2_wrong_ecc::{
-        integer::rns::Common,
+        integer::{rns::Common, AssignedInteger, Integer, IntegerInstructions as _, Range},
         maingate::{
             CombinationOption, CombinationOptionCommon, MainGate, MainGateInstructions, RegionCtx,
             Term,
         },
         AssignedPoint, BaseFieldEccChip,
     };
+    use num_bigint::BigUint;
     use rand::rngs::OsRng;
+    use std::iter;
 
     impl<'a, F: FieldExt> Context for RegionCtx<'a, F> {
         fn constrain_equal(&mut self, lhs: Cell, rhs: Cell) -> Result<(), Error> {
@@ -166,6 +553,37 @@ mod halo2_wrong {
             scalar
         }
 
+        fn value_of(&self, integer: &Self::AssignedInteger) -> Value<F> {
+            integer.value().cloned()
+        }
+
+        fn limbs(&self, integer: &Self::AssignedInteger) -> Vec<Value<F>> {
+            vec![integer.value().cloned()]
+        }
+
+        fn limb_info(&self) -> LimbInfo {
+            LimbInfo {
+                num_limbs: 1,
+                bits_per_limb: F::NUM_BITS as usize,
+            }
+        }
+
+        fn native_cell(&self, integer: &Self::AssignedInteger) -> Option<AssignedCell<F, F>> {
+            Some(integer.clone())
+        }
+
+        fn from_limbs(
+            &self,
+            _: &mut Self::Context,
+            limbs: Vec<AssignedCell<F, F>>,
+        ) -> Result<Self::AssignedInteger, Error> {
+            // Single limb spans the whole field, so there's no range to check
+            limbs
+                .into_iter()
+                .exactly_one()
+                .map_err(|_| Error::Synthesis)
+        }
+
         fn assign_integer(
             &self,
             ctx: &mut Self::Context,
@@ -182,6 +600,25 @@ mod halo2_wrong {
             MainGateInstructions::assign_constant(self, ctx, integer)
         }
 
+        fn expose_public(
+            &self,
+            layouter: impl Layouter<F>,
+            integer: Self::AssignedInteger,
+            row: usize,
+        ) -> Result<(), Error> {
+            MainGateInstructions::expose_public(self, layouter, integer, row)
+        }
+
+        fn copy(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+        ) -> Result<Self::AssignedInteger, Error> {
+            let copied = self.assign_value(ctx, a.value().cloned())?;
+            ctx.constrain_equal(a.cell(), copied.cell())?;
+            Ok(copied)
+        }
+
         fn sum_with_coeff_and_const(
             &self,
             ctx: &mut Self::Context,
@@ -279,6 +716,34 @@ mod halo2_wrong {
             }
         }
 
+        fn mul_add(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+            b: &Self::AssignedInteger,
+            c: &Self::AssignedInteger,
+        ) -> Result<Self::AssignedInteger, Error> {
+            let output = a
+                .value()
+                .zip(b.value())
+                .zip(c.value())
+                .map(|((a, b), c)| *a * b + c);
+            Ok(self
+                .apply(
+                    ctx,
+                    [
+                        Term::assigned_to_mul(a),
+                        Term::assigned_to_mul(b),
+                        Term::assigned_to_add(c),
+                        Term::Zero,
+                        Term::unassigned_to_sub(output),
+                    ],
+                    F::zero(),
+                    CombinationOptionCommon::OneLinerMul.into(),
+                )?
+                .swap_remove(4))
+        }
+
         fn sub(
             &self,
             ctx: &mut Self::Context,
@@ -304,6 +769,78 @@ mod halo2_wrong {
             MainGateInstructions::invert_unsafe(self, ctx, a)
         }
 
+        fn div(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+            b: &Self::AssignedInteger,
+        ) -> Result<Self::AssignedInteger, Error> {
+            let mut is_zero = false;
+            b.value().map(|b| is_zero = *b == F::zero());
+            if is_zero {
+                return Err(Error::Synthesis);
+            }
+            MainGateInstructions::div_unsafe(self, ctx, a, b)
+        }
+
+        fn invert_with_witness(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+            a_inv: Value<F>,
+        ) -> Result<Self::AssignedInteger, Error> {
+            Ok(self
+                .apply(
+                    ctx,
+                    [Term::assigned_to_mul(a), Term::unassigned_to_mul(a_inv)],
+                    -F::one(),
+                    CombinationOptionCommon::OneLinerMul.into(),
+                )?
+                .swap_remove(1))
+        }
+
+        fn add_with_witness(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+            b: &Self::AssignedInteger,
+            sum: Value<F>,
+        ) -> Result<Self::AssignedInteger, Error> {
+            Ok(self
+                .apply(
+                    ctx,
+                    [
+                        Term::assigned_to_add(a),
+                        Term::assigned_to_add(b),
+                        Term::unassigned_to_sub(sum),
+                    ],
+                    F::zero(),
+                    CombinationOptionCommon::OneLinerAdd.into(),
+                )?
+                .swap_remove(2))
+        }
+
+        fn mul_with_witness(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+            b: &Self::AssignedInteger,
+            product: Value<F>,
+        ) -> Result<Self::AssignedInteger, Error> {
+            Ok(self
+                .apply(
+                    ctx,
+                    [
+                        Term::assigned_to_mul(a),
+                        Term::assigned_to_mul(b),
+                        Term::unassigned_to_sub(product),
+                    ],
+                    F::zero(),
+                    CombinationOptionCommon::OneLinerMul.into(),
+                )?
+                .swap_remove(2))
+        }
+
         fn assert_equal(
             &self,
             ctx: &mut Self::Context,
@@ -317,6 +854,168 @@ mod halo2_wrong {
             MainGateInstructions::assert_equal(self, ctx, a, b)
                 .and(eq.then_some(()).ok_or(Error::Synthesis))
         }
+
+        fn assert_zero(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+        ) -> Result<(), Error> {
+            let mut eq = true;
+            a.value().map(|a| eq &= *a == F::zero());
+            MainGateInstructions::assert_zero(self, ctx, a)
+                .and(eq.then_some(()).ok_or(Error::Synthesis))
+        }
+
+        fn assert_one(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+        ) -> Result<(), Error> {
+            let mut eq = true;
+            a.value().map(|a| eq &= *a == F::one());
+            MainGateInstructions::assert_one(self, ctx, a)
+                .and(eq.then_some(()).ok_or(Error::Synthesis))
+        }
+
+        fn assert_bit(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+        ) -> Result<(), Error> {
+            MainGateInstructions::assert_bit(self, ctx, a)
+        }
+
+        fn is_equal(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+            b: &Self::AssignedInteger,
+        ) -> Result<Self::AssignedInteger, Error> {
+            MainGateInstructions::is_equal(self, ctx, a, b)
+        }
+
+        fn to_bits(
+            &self,
+            ctx: &mut Self::Context,
+            a: &Self::AssignedInteger,
+            num_bits: usize,
+        ) -> Result<Vec<Self::AssignedInteger>, Error> {
+            MainGateInstructions::to_bits(self, ctx, a, num_bits)
+        }
+
+        fn assert_bytes_le(
+            &self,
+            ctx: &mut Self::Context,
+            bytes: &[Self::AssignedInteger],
+        ) -> Result<(), Error> {
+            let bits = bytes
+                .iter()
+                .map(|byte| MainGateInstructions::to_bits(self, ctx, byte, 8))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect_vec();
+            if bits.len() < F::NUM_BITS as usize {
+                return Ok(());
+            }
+
+            // Compare against the modulus from the most significant bit, where
+            // `eq` tracks whether all higher bits equal to the modulus' and `lt`
+            // whether a higher bit already decided the composition is smaller.
+            let modulus = modulus::<F>();
+            let (mut eq, mut lt) = (None::<AssignedCell<F, F>>, None::<AssignedCell<F, F>>);
+            for (idx, bit) in bits.iter().enumerate().rev() {
+                let not_bit = self.not(ctx, bit)?;
+                if modulus.bit(idx as u64) {
+                    let term = match &eq {
+                        Some(eq) => self.and(ctx, eq, &not_bit)?,
+                        None => not_bit,
+                    };
+                    lt = Some(match lt {
+                        Some(lt) => MainGateInstructions::add(self, ctx, &lt, &term)?,
+                        None => term,
+                    });
+                    eq = Some(match eq {
+                        Some(eq) => self.and(ctx, &eq, bit)?,
+                        None => bit.clone(),
+                    });
+                } else {
+                    eq = Some(match eq {
+                        Some(eq) => self.and(ctx, &eq, &not_bit)?,
+                        None => not_bit,
+                    });
+                }
+            }
+            self.assert_one(ctx, &lt.unwrap())
+        }
+    }
+
+    /// Constrains a normalized `integer` to be at most `max` by subtracting it
+    /// from `max` limb by limb, with borrows witnessed as bits and each
+    /// difference decomposed into `BITS` bits, which a negative top
+    /// difference wraps around and fails.
+    fn assert_at_most<C: CurveAffine, const LIMBS: usize, const BITS: usize>(
+        main_gate: &MainGate<C::Scalar>,
+        ctx: &mut RegionCtx<'_, C::Scalar>,
+        integer: &AssignedInteger<C::Base, C::Scalar, LIMBS, BITS>,
+        max: &BigUint,
+    ) -> Result<(), Error> {
+        let mask = (BigUint::from(1u64) << BITS) - 1u64;
+        let max_limbs = (0..LIMBS)
+            .map(|idx| (max >> (idx * BITS)) & &mask)
+            .collect_vec();
+        let limbs = integer
+            .limbs()
+            .iter()
+            .map(|limb| limb.as_ref().clone())
+            .collect_vec();
+
+        // Borrow out of every limb but the top one
+        let mut borrow = Value::known(false);
+        let mut borrows = Vec::with_capacity(LIMBS - 1);
+        for (limb, max_limb) in limbs.iter().zip(max_limbs.iter()).take(LIMBS - 1) {
+            borrow = limb
+                .value()
+                .zip(borrow)
+                .map(|(limb, borrow)| fe_to_big(*limb) + u64::from(borrow) > *max_limb);
+            let bit = borrow.map(|borrow| C::Scalar::from(u64::from(borrow)));
+            borrows.push(MainGateInstructions::assign_bit(main_gate, ctx, bit)?);
+        }
+
+        let shift = fe_from_big::<C::Scalar>(BigUint::from(1u64) << BITS);
+        for (idx, (limb, max_limb)) in limbs.into_iter().zip(max_limbs).enumerate() {
+            let terms = iter::once((-C::Scalar::one(), limb))
+                .chain(
+                    idx.checked_sub(1)
+                        .map(|idx| (-C::Scalar::one(), borrows[idx].clone())),
+                )
+                .chain(borrows.get(idx).map(|borrow| (shift, borrow.clone())))
+                .collect_vec();
+            let diff = IntegerInstructions::sum_with_coeff_and_const(
+                main_gate,
+                ctx,
+                &terms,
+                fe_from_big(max_limb),
+            )?;
+            IntegerInstructions::to_bits(main_gate, ctx, &diff, BITS)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the native value of a coordinate, which is its value reduced
+    /// by the scalar field modulus. When the base field is the larger one,
+    /// the coordinate is constrained to be below the scalar field modulus,
+    /// so the reduction is a no-op and distinct coordinates stay distinct.
+    fn coordinate_to_native<C: CurveAffine, const LIMBS: usize, const BITS: usize>(
+        main_gate: &MainGate<C::Scalar>,
+        ctx: &mut RegionCtx<'_, C::Scalar>,
+        coordinate: &AssignedInteger<C::Base, C::Scalar, LIMBS, BITS>,
+    ) -> Result<AssignedCell<C::Scalar, C::Scalar>, Error> {
+        let scalar_modulus = modulus::<C::Scalar>();
+        if modulus::<C::Base>() > scalar_modulus {
+            assert_at_most::<C, LIMBS, BITS>(main_gate, ctx, coordinate, &(scalar_modulus - 1u64))?;
+        }
+        Ok(coordinate.native().clone())
     }
 
     impl<'a, C: CurveAffine, const LIMBS: usize, const BITS: usize> EccInstructions<'a, C>
@@ -327,11 +1026,30 @@ mod halo2_wrong {
         type AssignedEcPoint = AssignedPoint<C::Base, C::Scalar, LIMBS, BITS>;
         type Scalar = C::Scalar;
         type AssignedScalar = AssignedCell<C::Scalar, C::Scalar>;
+        type AssignedBase = AssignedInteger<C::Base, C::Scalar, LIMBS, BITS>;
 
         fn scalar_chip(&self) -> &Self::ScalarChip {
             self.main_gate()
         }
 
+        fn validate_params(&self) -> Result<(), String> {
+            let base_bits = C::Base::NUM_BITS as usize;
+            let scalar_bits = C::Scalar::NUM_BITS as usize;
+            if LIMBS * BITS < base_bits {
+                return Err(format!(
+                    "{} limbs of {} bits can't represent base field of {} bits",
+                    LIMBS, BITS, base_bits
+                ));
+            }
+            if 2 * BITS + LIMBS.next_power_of_two().trailing_zeros() as usize >= scalar_bits {
+                return Err(format!(
+                    "Product of limbs of {} bits overflows scalar field of {} bits",
+                    BITS, scalar_bits
+                ));
+            }
+            Ok(())
+        }
+
         fn assign_constant(
             &self,
             ctx: &mut Self::Context,
@@ -348,6 +1066,61 @@ mod halo2_wrong {
             self.assign_point(ctx, point)
         }
 
+        fn is_identity(
+            &self,
+            ctx: &mut Self::Context,
+            point: &Self::AssignedEcPoint,
+        ) -> Result<Self::AssignedScalar, Error> {
+            // Identity has no affine form, so a point constrained to be on
+            // curve is never the identity
+            self.assert_is_on_curve(ctx, point)?;
+            MainGateInstructions::assign_constant(self.main_gate(), ctx, C::Scalar::zero())
+        }
+
+        fn assert_not_identity(
+            &self,
+            ctx: &mut Self::Context,
+            point: &Self::AssignedEcPoint,
+        ) -> Result<(), Error> {
+            self.assert_is_on_curve(ctx, point)
+        }
+
+        fn x_coordinate(
+            &self,
+            ctx: &mut Self::Context,
+            point: &Self::AssignedEcPoint,
+        ) -> Result<Self::AssignedScalar, Error> {
+            coordinate_to_native::<C, LIMBS, BITS>(self.main_gate(), ctx, point.x())
+        }
+
+        fn y_coordinate(
+            &self,
+            ctx: &mut Self::Context,
+            point: &Self::AssignedEcPoint,
+        ) -> Result<Self::AssignedScalar, Error> {
+            coordinate_to_native::<C, LIMBS, BITS>(self.main_gate(), ctx, point.y())
+        }
+
+        fn scalar_to_base(
+            &self,
+            ctx: &mut Self::Context,
+            scalar: &Self::AssignedScalar,
+        ) -> Result<Self::AssignedBase, Error> {
+            let rns = self.integer_chip().rns();
+            let integer = scalar
+                .value()
+                .map(|scalar| Integer::from_fe(fe_to_fe(*scalar), rns.clone()));
+            let assigned =
+                self.integer_chip()
+                    .assign_integer(ctx, integer.into(), Range::Remainder)?;
+            ctx.constrain_equal(assigned.native().cell(), scalar.cell())?;
+            // Limbs only agree with the scalar modulo the scalar field, so the
+            // integer is bounded to be the scalar itself and a reduced base
+            let max = modulus::<C::Scalar>().min(modulus::<C::Base>()) - 1u64;
+            assert_at_most::<C, LIMBS, BITS>(self.main_gate(), ctx, &assigned, &max)?;
+            Ok(assigned)
+        }
+
         fn add(
             &self,
             ctx: &mut Self::Context,
@@ -357,6 +1130,94 @@ mod halo2_wrong {
             self.add(ctx, p0, p1)
         }
 
+        fn add_complete(
+            &self,
+            ctx: &mut Self::Context,
+            p0: &Self::AssignedEcPoint,
+            p1: &Self::AssignedEcPoint,
+        ) -> Result<Self::AssignedEcPoint, Error> {
+            let dx = self.integer_chip().sub(ctx, p1.x(), p0.x())?;
+            let (_, x_eq) = self.integer_chip().invert(ctx, &dx)?;
+
+            // Identity is not representable, so when x-coordinates are equal
+            // the points are constrained to be equal as well
+            let p1_or_p0 = self.select(ctx, &x_eq, p1, p0)?;
+            self.assert_equal(ctx, &p1_or_p0, p0)?;
+
+            let doubled = self.double(ctx, p0)?;
+            let rhs = self.select(ctx, &x_eq, &doubled, p1)?;
+            let sum = self.add(ctx, p0, &rhs)?;
+            self.select(ctx, &x_eq, &doubled, &sum)
+        }
+
+        fn neg(
+            &self,
+            ctx: &mut Self::Context,
+            point: &Self::AssignedEcPoint,
+        ) -> Result<Self::AssignedEcPoint, Error> {
+            self.neg(ctx, point)
+        }
+
+        fn is_equal(
+            &self,
+            ctx: &mut Self::Context,
+            p0: &Self::AssignedEcPoint,
+            p1: &Self::AssignedEcPoint,
+        ) -> Result<Self::AssignedScalar, Error> {
+            let dx = self.integer_chip().sub(ctx, p0.x(), p1.x())?;
+            let dy = self.integer_chip().sub(ctx, p0.y(), p1.y())?;
+            let (_, x_eq) = self.integer_chip().invert(ctx, &dx)?;
+            let (_, y_eq) = self.integer_chip().invert(ctx, &dy)?;
+            self.main_gate().and(ctx, &x_eq, &y_eq)
+        }
+
+        fn double(
+            &self,
+            ctx: &mut Self::Context,
+            point: &Self::AssignedEcPoint,
+        ) -> Result<Self::AssignedEcPoint, Error> {
+            self.double(ctx, point)
+        }
+
+        fn supports_add_window(&self) -> bool {
+            true
+        }
+
+        fn add_window(
+            &self,
+            ctx: &mut Self::Context,
+            acc: Option<&Self::AssignedEcPoint>,
+            table: &[Self::AssignedEcPoint],
+            bits: &[Self::AssignedScalar],
+        ) -> Result<Self::AssignedEcPoint, Error> {
+            if table.len() != 1 << bits.len() {
+                return Err(Error::Synthesis);
+            }
+
+            // Table is halved by each bit, least significant first
+            let mut candidates = table.to_vec();
+            for bit in bits {
+                candidates = candidates
+                    .chunks(2)
+                    .map(|pair| self.select(ctx, bit, &pair[1], &pair[0]))
+                    .collect::<Result<_, _>>()?;
+            }
+            let selected = candidates.pop().unwrap();
+
+            match acc {
+                Some(acc) => {
+                    let mut acc = acc.clone();
+                    for _ in 0..bits.len() {
+                        acc = self.double(ctx, &acc)?;
+                    }
+                    // Doubled sum might meet the selected point, so the
+                    // addition has to be complete
+                    EccInstructions::add_complete(self, ctx, &acc, &selected)
+                }
+                None => Ok(selected),
+            }
+        }
+
         fn multi_scalar_multiplication(
             &mut self,
             ctx: &mut Self::Context,
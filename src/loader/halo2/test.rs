@@ -1,8 +1,41 @@
 use crate::{
-    util::{arithmetic::CurveAffine, Itertools},
+    loader::{
+        self,
+        halo2::{CapacityHint, IntegerInstructions, ToNative},
+        native::NativeLoader,
+        EcPointLoader, LoadedEcPoint, LoadedScalar, ScalarLoader,
+    },
+    system::halo2::test::MainGateWithRangeConfig,
+    util::{
+        arithmetic::{
+            fe_to_fe, modulus, Curve, CurveAffine, Field, Group, PrimeCurveAffine, PrimeField,
+        },
+        transcript::Transcript,
+        Itertools,
+    },
     Protocol,
 };
-use halo2_proofs::circuit::Value;
+use halo2_curves::bn256::{Fq, Fr, G1Affine, G1};
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    dev::MockProver,
+    plonk::{self, Circuit},
+};
+use halo2_wrong_ecc::{
+    integer::rns::Rns,
+    maingate::{RangeInstructions, RegionCtx},
+};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+use std::{iter, rc::Rc};
+
+const K: u32 = 18;
+const LIMBS: usize = 4;
+const BITS: usize = 68;
+
+type BaseFieldEccChip = halo2_wrong_ecc::BaseFieldEccChip<G1Affine, LIMBS, BITS>;
+type Halo2Loader<'a> = loader::halo2::Halo2Loader<'a, G1Affine, BaseFieldEccChip>;
+type EcPoint<'a> = loader::halo2::EcPoint<'a, G1Affine, BaseFieldEccChip>;
+type Scalar<'a> = loader::halo2::Scalar<'a, G1Affine, BaseFieldEccChip>;
 
 pub struct Snark<C: CurveAffine> {
     pub protocol: Protocol<C>,
@@ -64,3 +97,2603 @@ impl<C: CurveAffine> SnarkWitness<C> {
         self.proof.as_ref().map(Vec::as_slice)
     }
 }
+
+#[derive(Clone)]
+struct LoaderCircuit(for<'a> fn(&Rc<Halo2Loader<'a>>));
+
+impl Circuit<Fr> for LoaderCircuit {
+    type Config = MainGateWithRangeConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut plonk::ConstraintSystem<Fr>) -> Self::Config {
+        MainGateWithRangeConfig::configure(
+            meta,
+            vec![BITS / LIMBS],
+            Rns::<Fq, Fr, LIMBS, BITS>::construct().overflow_lengths(),
+        )
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), plonk::Error> {
+        config.range_chip().load_table(&mut layouter)?;
+        let public_outputs = layouter.assign_region(
+            || "",
+            |region| {
+                let mut ctx = RegionCtx::new(region, 0);
+                let mut ecc_chip = config.ecc_chip();
+                let aux_generator = G1::random(ChaCha20Rng::seed_from_u64(0)).to_affine();
+                ecc_chip.assign_aux_generator(&mut ctx, Value::known(aux_generator))?;
+                let loader = Halo2Loader::new(ecc_chip, ctx);
+                (self.0)(&loader);
+                Ok(loader.take_public_outputs())
+            },
+        )?;
+
+        let main_gate = config.main_gate();
+        for (row, output) in public_outputs {
+            IntegerInstructions::expose_public(&main_gate, layouter.namespace(|| ""), output, row)?;
+        }
+        Ok(())
+    }
+}
+
+fn mock(test: for<'a> fn(&Rc<Halo2Loader<'a>>)) -> MockProver<Fr> {
+    mock_with_instances(test, Vec::new())
+}
+
+fn mock_with_instances(
+    test: for<'a> fn(&Rc<Halo2Loader<'a>>),
+    instances: Vec<Fr>,
+) -> MockProver<Fr> {
+    MockProver::run(K, &LoaderCircuit(test), vec![instances]).unwrap()
+}
+
+fn run(test: for<'a> fn(&Rc<Halo2Loader<'a>>)) {
+    mock(test).assert_satisfied();
+}
+
+#[test]
+fn test_preload_const_points() {
+    #[derive(Clone)]
+    struct PreloadCircuit;
+
+    impl Circuit<Fr> for PreloadCircuit {
+        type Config = MainGateWithRangeConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut plonk::ConstraintSystem<Fr>) -> Self::Config {
+            LoaderCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), plonk::Error> {
+            config.range_chip().load_table(&mut layouter)?;
+            let points = [
+                G1Affine::generator(),
+                (G1Affine::generator() * Fr::from(2)).into(),
+            ];
+            let dumped = layouter.assign_region(
+                || "",
+                |region| {
+                    let loader = Halo2Loader::new(config.ecc_chip(), RegionCtx::new(region, 0));
+                    loader.assign_const_ec_points(&points);
+                    Ok(loader.dump_const_points())
+                },
+            )?;
+            assert_eq!(dumped.len(), 2);
+            let preloaded = dumped
+                .iter()
+                .map(|(x, y)| G1Affine::from_xy(*x, *y).unwrap())
+                .collect_vec();
+
+            layouter.assign_region(
+                || "",
+                |region| {
+                    let loader = Halo2Loader::new(config.ecc_chip(), RegionCtx::new(region, 0));
+                    loader.preload_const_points(&preloaded).unwrap();
+                    assert_eq!(loader.num_const_ec_points(), 2);
+
+                    let offset = loader.ctx().offset();
+                    let num_ec_points = loader.num_ec_points();
+                    for point in points {
+                        loader.assign_const_ec_point(point);
+                    }
+                    assert_eq!(loader.ctx().offset(), offset);
+                    assert_eq!(loader.num_ec_points(), num_ec_points);
+
+                    let off_curve = G1Affine {
+                        x: Fq::one(),
+                        y: Fq::one(),
+                    };
+                    assert!(loader.preload_const_points(&[off_curve]).is_err());
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    MockProver::run(K, &PreloadCircuit, vec![Vec::new()])
+        .unwrap()
+        .assert_satisfied();
+}
+
+#[test]
+fn test_to_native() {
+    fn expression<L: LoadedScalar<Fr>>(a: &L, b: &L) -> L {
+        let loader = a.loader();
+        (a.clone() * b + loader.load_const(&Fr::from(3))) * a.invert().unwrap() - b
+    }
+
+    run(|loader| {
+        let [a, b] = [Fr::from(5), Fr::from(7)];
+        let expected = expression(&a, &b);
+        let output = expression(
+            &loader.assign_scalar(Value::known(a)),
+            &loader.assign_scalar(Value::known(b)),
+        );
+        output
+            .to_native()
+            .zip(expected.to_native())
+            .assert_if_known(|(output, expected)| output == expected);
+    });
+}
+
+#[test]
+fn test_muladd() {
+    run(|loader| {
+        let [a, b, c] = [2, 3, 5].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+
+        let offset = loader.ctx().offset();
+        let expected = a.clone() * &b + &c;
+        let naive_cost = loader.ctx().offset() - offset;
+
+        let offset = loader.ctx().offset();
+        let output = a.muladd(&b, &c);
+        let fused_cost = loader.ctx().offset() - offset;
+
+        assert!(fused_cost < naive_cost);
+        loader.assert_eq("", &output, &expected).unwrap();
+    });
+}
+
+#[test]
+fn test_sum_products_dedup() {
+    run(|loader| {
+        let xs = (0..5)
+            .map(|value| loader.assign_scalar(Value::known(Fr::from(value))))
+            .collect_vec();
+
+        let offset = loader.ctx().offset();
+        let constant = loader.load_const(&Fr::from(7));
+        loader.sum_products(&xs.iter().map(|x| (&constant, x)).collect_vec());
+        let cost = loader.ctx().offset() - offset;
+
+        let offset = loader.ctx().offset();
+        let assigned = loader.assign_const_scalar(Fr::from(7));
+        loader.sum_products(&xs.iter().map(|x| (&assigned, x)).collect_vec());
+        assert_eq!(cost, loader.ctx().offset() - offset);
+    });
+}
+
+#[test]
+fn test_chained_msm() {
+    run(|loader| {
+        let g = G1Affine::generator();
+        let h = G1Affine::from(g * Fr::from(5));
+        let [two, three] = [2, 3].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+
+        let intermediate = EcPoint::multi_scalar_multiplication([
+            (two, loader.ec_point_load_const(&g)),
+            (loader.load_one(), loader.ec_point_load_const(&h)),
+        ]);
+        assert!(!intermediate.is_normalized());
+        let output = EcPoint::multi_scalar_multiplication([
+            (loader.load_one(), intermediate),
+            (three, loader.ec_point_load_const(&g)),
+        ]);
+
+        let expected = loader.ec_point_load_const(&(g * Fr::from(10)).into());
+        loader.ec_point_assert_eq("", &output, &expected).unwrap();
+    });
+}
+
+#[test]
+fn test_assert_eq_detail() {
+    let prover = mock(|loader| {
+        let [lhs, rhs] = [1, 2].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        match loader.assert_eq("lhs == rhs", &lhs, &rhs) {
+            Err(crate::Error::AssertionFailureDetailed { annotation, detail }) => {
+                assert_eq!(annotation, "lhs == rhs");
+                assert!(detail.starts_with("limb 0 differs"));
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    });
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_linear_combination() {
+    run(|loader| {
+        let g = G1Affine::generator();
+        let [p0, p1, p2] = [2, 3, 5]
+            .map(|value| loader.assign_ec_point(Value::known((g * Fr::from(value)).into())));
+
+        let output = loader
+            .linear_combination(&[(Fr::from(7), &p0), (Fr::zero(), &p1), (Fr::one(), &p2)])
+            .unwrap();
+        let expected = EcPoint::multi_scalar_multiplication([
+            (loader.load_const(&Fr::from(7)), p0.clone()),
+            (loader.load_const(&Fr::one()), p2.clone()),
+        ]);
+        loader.ec_point_assert_eq("", &output, &expected).unwrap();
+
+        let identity = loader.ec_point_load_zero();
+        for terms in [&[][..], &[(Fr::zero(), &p0)][..]] {
+            let output = loader.linear_combination(terms).unwrap();
+            assert!(output.is_identity_const());
+            loader.ec_point_assert_eq("", &output, &identity).unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_mul_by_zero_and_one() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(5)));
+
+        let offset = loader.ctx().offset();
+        let zero = x.clone() * loader.load_zero();
+        let one = loader.load_one() * &x;
+        assert_eq!(loader.ctx().offset(), offset);
+
+        assert_eq!(one, x);
+        zero.to_native().assert_if_known(|zero| *zero == Fr::zero());
+    });
+}
+
+#[test]
+fn test_add_zero() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(5)));
+
+        let offset = loader.ctx().offset();
+        let mut acc = loader.load_zero();
+        acc += &x;
+        assert_eq!(loader.ctx().offset(), offset);
+        assert_eq!(acc, x);
+    });
+}
+
+#[test]
+fn test_identity() {
+    run(|loader| {
+        let p = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        let identity = loader.ec_point_identity();
+        assert_eq!(identity, identity.clone());
+
+        let output = EcPoint::multi_scalar_multiplication([
+            (loader.load_one(), p.clone()),
+            (loader.load_one(), identity.clone()),
+        ]);
+        loader.ec_point_assert_eq("", &output, &p).unwrap();
+        loader
+            .ec_point_assert_eq("", &loader.ec_point_load_zero(), &identity)
+            .unwrap();
+
+        identity
+            .is_identity()
+            .to_native()
+            .assert_if_known(|is_identity| *is_identity == Fr::one());
+        p.is_identity()
+            .to_native()
+            .assert_if_known(|is_identity| *is_identity == Fr::zero());
+    });
+}
+
+#[test]
+fn test_from_bytes_le() {
+    run(|loader| {
+        for value in [Fr::from(0x0102), -Fr::one()] {
+            let bytes = value
+                .to_repr()
+                .as_ref()
+                .iter()
+                .map(|byte| loader.assign_scalar(Value::known(Fr::from(*byte as u64))))
+                .collect_vec();
+            loader
+                .from_bytes_le(&bytes)
+                .to_native()
+                .assert_if_known(|decoded| *decoded == value);
+        }
+    });
+
+    let prover = mock(|loader| {
+        let mut bytes = modulus::<Fr>().to_bytes_le();
+        bytes.resize(32, 0);
+        let bytes = bytes
+            .into_iter()
+            .map(|byte| loader.assign_scalar(Value::known(Fr::from(byte as u64))))
+            .collect_vec();
+        loader.from_bytes_le(&bytes);
+    });
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_batch_invert() {
+    run(|loader| {
+        let mut values = [2, 3, 5]
+            .map(|value| loader.assign_scalar(Value::known(Fr::from(value))))
+            .into_iter()
+            .chain(Some(loader.load_const(&Fr::from(7))))
+            .collect_vec();
+        let expected = values
+            .iter()
+            .map(|value| LoadedScalar::invert(value).unwrap())
+            .collect_vec();
+
+        LoadedScalar::batch_invert(values.iter_mut());
+        for (value, expected) in values.iter().zip(expected) {
+            loader.assert_eq("", value, &expected).unwrap();
+        }
+
+        // Zero constants are left as is
+        let mut values = [loader.load_zero(), loader.load_const(&Fr::from(2))];
+        LoadedScalar::batch_invert(values.iter_mut());
+        assert_eq!(values[0].snapshot(), Some(Fr::zero()));
+        assert_eq!(values[1].snapshot(), Fr::from(2).invert().into());
+    });
+
+    // Assigned zero can't be inverted, while the rest of the batch still is
+    let prover = mock(|loader| {
+        let mut values = [0, 3]
+            .map(|value| loader.assign_scalar(Value::known(Fr::from(value))))
+            .to_vec();
+        LoadedScalar::batch_invert(values.iter_mut());
+        values[1]
+            .to_native()
+            .assert_if_known(|value| *value == Fr::from(3).invert().unwrap());
+    });
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_batch_add_and_mul() {
+    run(|loader| {
+        let pairs = [(2, 3), (5, 7)]
+            .map(|(lhs, rhs)| {
+                (
+                    loader.assign_scalar(Value::known(Fr::from(lhs))),
+                    loader.assign_scalar(Value::known(Fr::from(rhs))),
+                )
+            })
+            .into_iter()
+            .chain([(
+                loader.assign_scalar(Value::known(Fr::from(11))),
+                loader.load_const(&Fr::from(13)),
+            )])
+            .collect_vec();
+
+        let sums = loader.batch_add(&pairs).unwrap();
+        let products = loader.batch_mul(&pairs).unwrap();
+        for ((lhs, rhs), (sum, product)) in pairs.iter().zip(sums.iter().zip(products.iter())) {
+            loader.assert_eq("", sum, &(lhs.clone() + rhs)).unwrap();
+            loader.assert_eq("", product, &(lhs.clone() * rhs)).unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_ec_point_coordinates() {
+    run(|loader| {
+        let p: G1Affine = (G1Affine::generator() * Fr::from(42)).into();
+        let coordinates = p.coordinates().unwrap();
+        let [x, y] =
+            [coordinates.x(), coordinates.y()].map(|coordinate| fe_to_fe::<Fq, Fr>(*coordinate));
+
+        let p = loader.assign_ec_point(Value::known(p));
+        p.x()
+            .unwrap()
+            .to_native()
+            .assert_if_known(|value| *value == x);
+        p.y()
+            .unwrap()
+            .to_native()
+            .assert_if_known(|value| *value == y);
+    });
+}
+
+#[test]
+fn test_identity_coordinates() {
+    run(|loader| {
+        // halo2wrong can't assign the identity, so its coordinates are errors
+        let identity = loader.ec_point_load_zero();
+        assert!(identity.assigned().is_err());
+        assert!(identity.x().is_err());
+        assert!(identity.y().is_err());
+    });
+}
+
+#[test]
+fn test_ec_point_coordinate_above_scalar_modulus() {
+    use crate::util::arithmetic::fe_from_big;
+
+    let prover = mock(|loader| {
+        // Smallest valid x-coordinate that is at least the scalar modulus
+        let p = (0u64..)
+            .find_map(|k| {
+                let x = fe_from_big::<Fq>(modulus::<Fr>() + k);
+                Option::from(
+                    (x.square() * x + Fq::from(3))
+                        .sqrt()
+                        .and_then(|y| G1Affine::from_xy(x, y)),
+                )
+            })
+            .unwrap();
+
+        loader.assign_ec_point(Value::known(p)).x().unwrap();
+    });
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_absorb_ec_point() {
+    type PoseidonTranscript<L, S> =
+        crate::system::halo2::transcript::halo2::PoseidonTranscript<G1Affine, L, S, 5, 4, 8, 60>;
+
+    run(|loader| {
+        let p: G1Affine = (G1Affine::generator() * Fr::from(42)).into();
+        let expected = {
+            let mut transcript = PoseidonTranscript::<NativeLoader, _>::new(Vec::<u8>::new());
+            transcript.common_ec_point(&p).unwrap();
+            transcript.squeeze_challenge()
+        };
+
+        let mut transcript =
+            PoseidonTranscript::<Rc<Halo2Loader>, _>::new(loader, Value::known([].as_slice()));
+        transcript
+            .common_ec_point(&loader.assign_ec_point(Value::known(p)))
+            .unwrap();
+        transcript
+            .squeeze_challenge()
+            .to_native()
+            .assert_if_known(|challenge| *challenge == expected);
+    });
+}
+
+#[test]
+fn test_conditional_assert_eq() {
+    run(|loader| {
+        let [lhs, rhs] = [1, 2].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+
+        let offset = loader.ctx().offset();
+        loader
+            .conditional_assert_eq("", &loader.load_zero(), &lhs, &rhs)
+            .unwrap();
+        assert_eq!(loader.ctx().offset(), offset);
+
+        let cond = loader.assign_scalar(Value::known(Fr::zero()));
+        loader.conditional_assert_eq("", &cond, &lhs, &rhs).unwrap();
+    });
+
+    let prover = mock(|loader| {
+        let [lhs, rhs] = [1, 2].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        assert!(loader
+            .conditional_assert_eq("", &loader.load_one(), &lhs, &rhs)
+            .is_err());
+    });
+    assert!(prover.verify().is_err());
+
+    let prover = mock(|loader| {
+        let [lhs, rhs] = [1, 2].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        let cond = loader.assign_scalar(Value::known(Fr::one()));
+        assert!(loader.conditional_assert_eq("", &cond, &lhs, &rhs).is_err());
+    });
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_num_const_ec_points() {
+    run(|loader| {
+        let g = G1Affine::generator();
+        for value in [1, 2, 1, 2, 3] {
+            loader.ec_point_load_const(&(g * Fr::from(value)).into());
+        }
+        assert_eq!(loader.num_const_ec_points(), 3);
+        assert_eq!(loader.num_ec_points(), 3);
+    });
+}
+
+#[test]
+fn test_powers() {
+    run(|loader| {
+        let x = Fr::from(3);
+        for n in [0, 1, 2, 8] {
+            let expected = iter::successors(Some(Fr::one()), |power| Some(*power * x))
+                .take(n)
+                .collect_vec();
+
+            let offset = loader.ctx().offset();
+            let powers = ScalarLoader::powers(loader, &loader.load_const(&x), n);
+            assert_eq!(loader.ctx().offset(), offset);
+            assert_eq!(powers.len(), n);
+            for (power, expected) in powers.iter().zip(expected.iter()) {
+                power.to_native().assert_if_known(|power| power == expected);
+            }
+
+            let powers = ScalarLoader::powers(loader, &loader.assign_scalar(Value::known(x)), n);
+            assert_eq!(powers.len(), n);
+            for (power, expected) in powers.iter().zip(expected.iter()) {
+                power.to_native().assert_if_known(|power| power == expected);
+            }
+        }
+    });
+}
+
+#[test]
+fn test_negate_in_place() {
+    run(|loader| {
+        let p: G1Affine = (G1Affine::generator() * Fr::from(42)).into();
+        let expected = loader.assign_ec_point(Value::known(-p));
+
+        let mut unshared = loader.assign_ec_point(Value::known(p));
+        let num_ec_points = loader.num_ec_points();
+        unshared.negate_in_place();
+        assert_eq!(loader.num_ec_points(), num_ec_points);
+        loader.ec_point_assert_eq("", &unshared, &expected).unwrap();
+
+        let mut shared = loader.assign_ec_point(Value::known(p));
+        let original = shared.clone();
+        shared.negate_in_place();
+        loader.ec_point_assert_eq("", &shared, &expected).unwrap();
+        loader
+            .ec_point_assert_eq("", &original.neg(), &expected)
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_assign_const_ec_points() {
+    run(|loader| {
+        let g = G1Affine::generator();
+        let [p1, p2, p3] = [1, 2, 3].map(|value| G1Affine::from(g * Fr::from(value)));
+        loader.assign_const_ec_point(p1);
+
+        let points = loader.assign_const_ec_points(&[p2, p1, G1Affine::identity(), p2, p3]);
+        assert_eq!(loader.num_const_ec_points(), 3);
+        assert_eq!(points[0], points[3]);
+        assert_eq!(points[1], loader.assign_const_ec_point(p1));
+        assert!(points[2].is_identity_const());
+        for (point, expected) in points.iter().zip([p2, p1, G1Affine::identity(), p2, p3]) {
+            loader
+                .ec_point_assert_eq("", point, &loader.ec_point_load_const(&expected))
+                .unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_complete_addition() {
+    run(|loader| {
+        loader.set_complete_addition(true);
+
+        let p: G1Affine = (G1Affine::generator() * Fr::from(42)).into();
+        let expected = loader.ec_point_load_const(&(p * Fr::from(2)).into());
+
+        let p = loader.assign_ec_point(Value::known(p));
+        let doubled = EcPoint::multi_scalar_multiplication([
+            (loader.load_one(), p.clone()),
+            (loader.load_one(), p),
+        ]);
+        loader.ec_point_assert_eq("", &doubled, &expected).unwrap();
+    });
+}
+
+#[test]
+fn test_conditional_negate() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(42)));
+        let neg_x = -x.clone();
+
+        let negated = loader.conditional_negate(&x, &loader.load_zero()).unwrap();
+        assert_eq!(negated, x);
+        let negated = loader.conditional_negate(&x, &loader.load_one()).unwrap();
+        loader.assert_eq("", &negated, &neg_x).unwrap();
+        assert!(matches!(
+            loader.conditional_negate(&x, &loader.load_const(&Fr::from(2))),
+            Err(crate::Error::AssertionFailure(message)) if message == "bit is not boolean"
+        ));
+
+        for (bit, expected) in [(Fr::zero(), &x), (Fr::one(), &neg_x)] {
+            let bit = loader.assign_scalar(Value::known(bit));
+            let negated = loader.conditional_negate(&x, &bit).unwrap();
+            loader.assert_eq("", &negated, expected).unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_ec_point_mul() {
+    run(|loader| {
+        let p = loader.assign_ec_point(Value::known((G1Affine::generator() * Fr::from(42)).into()));
+        // Odd, even and the largest scalar, where even ones are recoded
+        // with the modulus added
+        let scalars = [Fr::from(1337), Fr::from(1338), -Fr::one()]
+            .map(|scalar| loader.assign_scalar(Value::known(scalar)));
+
+        // Multiplied before any msm, so nothing relies on an aux generator
+        let products = scalars.iter().map(|s| p.mul(s).unwrap()).collect_vec();
+        for (s, product) in scalars.iter().zip(products) {
+            let expected = EcPoint::multi_scalar_multiplication([(s.clone(), p.clone())]);
+            loader.ec_point_assert_eq("", &product, &expected).unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_region_of() {
+    run(|loader| {
+        let [x, y] = [2, 3].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        let z = x.clone() * &y + &x;
+        let p = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        let w = z.clone() * &z;
+
+        let scalar_regions =
+            [&x, &y, &z, &w].map(|scalar| loader.region_of(scalar.index()).unwrap());
+        for (lhs, rhs) in scalar_regions.iter().tuple_windows() {
+            assert!(lhs.0 <= lhs.1 && lhs.1 <= rhs.0);
+        }
+        let (start, end) = loader.ec_point_region_of(p.index()).unwrap();
+        assert!(scalar_regions[2].1 <= start && end <= scalar_regions[3].0);
+        assert!(start < end);
+        assert_eq!(loader.region_of(w.index() + 1), None);
+    });
+}
+
+#[test]
+fn test_assert_zero_and_one() {
+    run(|loader| {
+        let [zero, one] = [0, 1].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        loader.assert_zero("", &zero).unwrap();
+        loader.assert_one("", &one).unwrap();
+        loader.assert_zero("", &loader.load_zero()).unwrap();
+        loader.assert_one("", &loader.load_one()).unwrap();
+        assert!(loader.assert_zero("", &loader.load_one()).is_err());
+        assert!(loader.assert_one("", &loader.load_zero()).is_err());
+    });
+
+    let prover = mock(|loader| {
+        let [zero, one] = [0, 1].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        assert!(loader.assert_zero("", &one).is_err());
+        assert!(loader.assert_one("", &zero).is_err());
+    });
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_div() {
+    run(|loader| {
+        let [a, b] = [3, 7].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        let c = loader.load_const(&Fr::from(5));
+        for (lhs, rhs) in [(&a, &b), (&a, &c), (&c, &b), (&c, &c)] {
+            let quotient = lhs.clone() / rhs;
+            let expected = lhs.clone() * LoadedScalar::invert(rhs).unwrap();
+            loader.assert_eq("", &quotient, &expected).unwrap();
+        }
+
+        let zero = loader.assign_scalar(Value::known(Fr::zero()));
+        assert!(loader.try_div(&a, &zero).is_err());
+        assert!(loader.try_div(&a, &loader.load_zero()).is_err());
+    });
+}
+
+#[test]
+fn test_load_u64_and_u128() {
+    run(|loader| {
+        let [a, b] = [3, 4].map(|value| loader.load_u64(value));
+        loader
+            .assert_eq("", &(a * b), &loader.load_const(&Fr::from(12)))
+            .unwrap();
+
+        let c = loader.load_u128(u128::MAX);
+        let expected = loader.load_const(
+            &(Fr::from(u64::MAX) * Fr::from(u64::MAX) + Fr::from(2) * Fr::from(u64::MAX)),
+        );
+        loader.assert_eq("", &c, &expected).unwrap();
+        loader
+            .assert_eq("", &loader.load_u128(7), &loader.load_u64(7))
+            .unwrap();
+    });
+}
+
+#[cfg(feature = "op_trace")]
+#[test]
+fn test_op_trace_replay() {
+    use crate::loader::halo2::trace::{replay, Op};
+
+    run(|loader| {
+        let [a, b] = [3, 5].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        let c = a.clone() * &b + &a;
+        let d = LoadedScalar::invert(&c).unwrap();
+        let generator = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        let msm = EcPoint::multi_scalar_multiplication([(d.clone(), generator)]);
+        loader
+            .assert_eq("", &(d.clone() * &c), &loader.load_one())
+            .unwrap();
+
+        let trace = loader.take_trace();
+        assert!(matches!(trace.first(), Some(Op::AssignScalar { .. })));
+        assert!(loader.take_trace().is_empty());
+
+        let replayed = replay(&trace, loader).unwrap();
+        for scalar in [a, b, c, d] {
+            loader
+                .assert_eq("", &replayed.scalars[&scalar.index()], &scalar)
+                .unwrap();
+        }
+        loader
+            .ec_point_assert_eq("", &replayed.ec_points[&msm.index()], &msm)
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_random_linear_combine() {
+    run(|loader| {
+        let points = (1..4)
+            .map(|scalar| {
+                let point = (G1Affine::generator() * Fr::from(scalar)).into();
+                loader.assign_ec_point(Value::known(point))
+            })
+            .collect_vec();
+        let r = loader.assign_scalar(Value::known(Fr::from(7)));
+
+        let one = loader.load_one();
+        let horner = points[1..].iter().fold(points[0].clone(), |acc, point| {
+            EcPoint::multi_scalar_multiplication([
+                (one.clone(), acc.mul(&r).unwrap()),
+                (one.clone(), point.clone()),
+            ])
+        });
+        let flattened = loader.random_linear_combine(&points, &r);
+        loader.ec_point_assert_eq("", &horner, &flattened).unwrap();
+
+        let expected = loader.assign_ec_point(Value::known(
+            (G1Affine::generator() * Fr::from(49 + 2 * 7 + 3)).into(),
+        ));
+        loader
+            .ec_point_assert_eq("", &flattened, &expected)
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_is_equal() {
+    run(|loader| {
+        let [a, b, c] = [3, 3, 5].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        let [three, five] = [3, 5].map(|value| loader.load_const(&Fr::from(value)));
+        for (lhs, rhs, expected) in [
+            (&a, &b, loader.load_one()),
+            (&a, &c, loader.load_zero()),
+            (&a, &three, loader.load_one()),
+            (&five, &a, loader.load_zero()),
+        ] {
+            loader
+                .assert_eq("", &loader.is_equal(lhs, rhs), &expected)
+                .unwrap();
+        }
+
+        let offset = loader.ctx().offset();
+        loader
+            .is_equal(&three, &three)
+            .to_native()
+            .assert_if_known(|is_equal| *is_equal == Fr::one());
+        loader
+            .is_equal(&three, &five)
+            .to_native()
+            .assert_if_known(|is_equal| *is_equal == Fr::zero());
+        assert_eq!(loader.ctx().offset(), offset);
+    });
+}
+
+#[test]
+fn test_scalar_limb_info() {
+    run(|loader| {
+        assert_eq!(
+            loader.scalar_limb_info(),
+            loader::halo2::LimbInfo {
+                num_limbs: 1,
+                bits_per_limb: Fr::NUM_BITS as usize,
+            }
+        );
+    });
+}
+
+#[test]
+fn test_long_sum() {
+    run(|loader| {
+        let values = (1..=256)
+            .map(|value| loader.assign_scalar(Value::known(Fr::from(value))))
+            .collect_vec();
+        let sum = loader.sum_with_const(&values.iter().collect_vec(), Fr::from(7));
+        loader
+            .assert_eq("", &sum, &loader.load_const(&Fr::from(256 * 257 / 2 + 7)))
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_max_sum_terms() {
+    let native = loader::halo2::LimbInfo {
+        num_limbs: 1,
+        bits_per_limb: 254,
+    };
+    assert_eq!(native.max_sum_terms(254), usize::MAX);
+
+    let non_native = loader::halo2::LimbInfo {
+        num_limbs: 4,
+        bits_per_limb: 68,
+    };
+    assert_eq!(non_native.max_sum_terms(72), 8);
+    assert_eq!(non_native.max_sum_terms(254), usize::MAX);
+    assert_eq!(non_native.max_sum_terms(68), 2);
+}
+
+#[test]
+fn test_points_equal() {
+    run(|loader| {
+        let generator = G1Affine::generator();
+        let doubled = (generator * Fr::from(2)).into();
+        let [a, b, c] = [generator, generator, doubled]
+            .map(|point| loader.assign_ec_point(Value::known(point)));
+        loader
+            .assert_eq("", &loader.points_equal(&a, &b), &loader.load_one())
+            .unwrap();
+        loader
+            .assert_eq("", &loader.points_equal(&a, &c), &loader.load_zero())
+            .unwrap();
+
+        let identity = loader.ec_point_load_zero();
+        loader
+            .assert_eq(
+                "",
+                &loader.points_equal(&identity, &identity),
+                &loader.load_one(),
+            )
+            .unwrap();
+        loader
+            .assert_eq("", &loader.points_equal(&a, &identity), &loader.load_zero())
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_lazy_scalar() {
+    run(|loader| {
+        let (offset, num_scalar) = (loader.ctx().offset(), loader.load_zero().index() + 1);
+        let unused = loader.assign_scalar_lazily(Value::known(Fr::from(3)));
+        let used = loader.assign_scalar_lazily(Value::known(Fr::from(5)));
+        assert_eq!(loader.ctx().offset(), offset);
+        assert_eq!(loader.load_zero().index(), num_scalar);
+        assert!(!unused.is_assigned());
+
+        let scalar = used.clone().scalar();
+        assert!(used.is_assigned());
+        assert_eq!(used.scalar(), scalar);
+        loader
+            .assert_eq("", &scalar, &loader.load_const(&Fr::from(5)))
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_batch_horner() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(3)));
+        let coeff_sets = [vec![1, 2, 3], vec![4], vec![5, 6, 7, 8]].map(|coeffs| {
+            coeffs
+                .into_iter()
+                .map(|coeff| loader.assign_scalar(Value::known(Fr::from(coeff))))
+                .collect_vec()
+        });
+        let evals = loader.batch_horner(&coeff_sets.iter().map(Vec::as_slice).collect_vec(), &x);
+        for (coeffs, eval) in coeff_sets.iter().zip(evals) {
+            let expected = coeffs
+                .iter()
+                .rev()
+                .fold(loader.load_zero(), |acc, coeff| acc * &x + coeff);
+            loader.assert_eq("", &eval, &expected).unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_load_const_hex() {
+    run(|loader| {
+        for (hex, expected) in [
+            ("0x0102", Fr::from(0x0102)),
+            ("102", Fr::from(0x0102)),
+            ("ff", Fr::from(0xff)),
+        ] {
+            let scalar = loader.load_const_hex(hex).unwrap();
+            loader
+                .assert_eq("", &scalar, &loader.load_const(&expected))
+                .unwrap();
+        }
+
+        let modulus_plus_one = format!("{:x}", modulus::<Fr>() + 1u64);
+        let reduced = loader.load_const_hex(&modulus_plus_one).unwrap();
+        loader.assert_eq("", &reduced, &loader.load_one()).unwrap();
+
+        for hex in ["", "0x", "0xzz", "12 34"] {
+            assert!(matches!(
+                loader.load_const_hex(hex),
+                Err(crate::Error::Parse(_))
+            ));
+        }
+    });
+}
+
+#[test]
+fn test_assert_msm_arity() {
+    run(|loader| {
+        assert!(loader.assert_msm_arity(0).is_err());
+
+        let generator = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        let scalar = loader.assign_scalar(Value::known(Fr::from(3)));
+        EcPoint::multi_scalar_multiplication([
+            (scalar.clone(), generator.clone()),
+            (scalar, loader.ec_point_load_zero()),
+        ]);
+        loader.assert_msm_arity(2).unwrap();
+        assert!(matches!(
+            loader.assert_msm_arity(3),
+            Err(crate::Error::AssertionFailureDetailed { .. })
+        ));
+    });
+}
+
+#[test]
+fn test_sum_and_product_constant_folding() {
+    run(|loader| {
+        let [a, b] = [3, 5].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        let [two, seven] = [2, 7].map(|value| loader.load_const(&Fr::from(value)));
+
+        let offset = loader.ctx().offset();
+        loader.sum(&[&a, &b]);
+        let sum_rows = loader.ctx().offset() - offset;
+        loader.product(&[&a, &b]);
+        let product_rows = loader.ctx().offset() - offset - sum_rows;
+
+        let offset = loader.ctx().offset();
+        let sum = loader.sum(&[&two, &a, &seven, &b]);
+        assert_eq!(loader.ctx().offset() - offset, sum_rows);
+        let offset = loader.ctx().offset();
+        let product = loader.product(&[&two, &a, &seven, &b]);
+        assert_eq!(loader.ctx().offset() - offset, product_rows);
+
+        loader
+            .assert_eq("", &sum, &loader.load_const(&Fr::from(17)))
+            .unwrap();
+        loader
+            .assert_eq("", &product, &loader.load_const(&Fr::from(210)))
+            .unwrap();
+
+        let offset = loader.ctx().offset();
+        loader
+            .sum(&[])
+            .to_native()
+            .assert_if_known(|sum| *sum == Fr::zero());
+        loader
+            .product(&[])
+            .to_native()
+            .assert_if_known(|product| *product == Fr::one());
+        loader.sum(&[&two, &seven]);
+        loader.product(&[&two, &seven]);
+        loader.product(&[&a, &loader.load_zero()]);
+        assert_eq!(loader.ctx().offset(), offset);
+    });
+}
+
+#[test]
+fn test_decide_kzg_accumulator() {
+    use crate::pcs::{
+        kzg::{Gwc19, Kzg, KzgAccumulator, KzgDecidingKey, LimbsEncoding},
+        AccumulatorEncoding, Decider,
+    };
+    use halo2_curves::bn256::{Bn256, G2Affine};
+
+    type Pcs = Kzg<Bn256, Gwc19>;
+
+    let s = Fr::from(11);
+    let dk =
+        KzgDecidingKey::<Bn256>::new(G2Affine::generator(), (G2Affine::generator() * s).into());
+    let rhs: G1Affine = (G1Affine::generator() * Fr::from(5)).into();
+    let lhs: G1Affine = (rhs * s).into();
+    assert!(<Pcs as Decider<_, NativeLoader>>::decide(
+        &dk,
+        KzgAccumulator::new(lhs, rhs)
+    ));
+    assert!(!<Pcs as Decider<_, NativeLoader>>::decide(
+        &dk,
+        KzgAccumulator::new(rhs, lhs)
+    ));
+
+    run(|loader| {
+        let s = Fr::from(11);
+        let dk =
+            KzgDecidingKey::<Bn256>::new(G2Affine::generator(), (G2Affine::generator() * s).into());
+        let rhs: G1Affine = (G1Affine::generator() * Fr::from(5)).into();
+        let lhs: G1Affine = (rhs * s).into();
+        let [lhs, rhs] = [lhs, rhs].map(|point| loader.assign_ec_point(Value::known(point)));
+
+        let limbs = Pcs::decide(&dk, KzgAccumulator::new(lhs.clone(), rhs.clone())).unwrap();
+        assert_eq!(limbs.len(), 4 * LIMBS);
+
+        let limbs = limbs
+            .into_iter()
+            .map(|limb| loader.scalar_from_instance(limb))
+            .collect_vec();
+        let decoded =
+            <LimbsEncoding<LIMBS, BITS> as AccumulatorEncoding<_, _, Pcs>>::from_repr(limbs)
+                .unwrap();
+        loader.ec_point_assert_eq("", &decoded.lhs, &lhs).unwrap();
+        loader.ec_point_assert_eq("", &decoded.rhs, &rhs).unwrap();
+    });
+}
+
+#[test]
+fn test_copy_to_new_cell() {
+    run(|loader| {
+        let scalar = loader.assign_scalar(Value::known(Fr::from(3)));
+        let copied = scalar.copy_to_new_cell();
+        assert_ne!(copied.index(), scalar.index());
+        assert_ne!(copied.assigned().cell(), scalar.assigned().cell());
+        loader.assert_eq("", &copied, &scalar).unwrap();
+
+        let constant = loader.load_const(&Fr::from(5)).copy_to_new_cell();
+        loader
+            .assert_eq("", &constant, &loader.load_const(&Fr::from(5)))
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_to_bits() {
+    run(|loader| {
+        for value in [0, 1, 6, 255] {
+            for x in [
+                loader.assign_scalar(Value::known(Fr::from(value))),
+                loader.load_const(&Fr::from(value)),
+            ] {
+                let bits = loader.to_bits(&x, 8).unwrap();
+                assert_eq!(bits.len(), 8);
+                let coeffs = iter::successors(Some(Fr::one()), |coeff| Some(coeff.double()));
+                let composed = loader
+                    .sum_with_coeff_and_const(&coeffs.zip(bits.iter()).collect_vec(), Fr::zero());
+                loader.assert_eq("", &composed, &x).unwrap();
+            }
+        }
+
+        assert!(matches!(
+            loader.to_bits(&loader.load_const(&Fr::from(256)), 8),
+            Err(crate::Error::AssertionFailure(_))
+        ));
+    });
+}
+
+#[test]
+fn test_msm_accumulation() {
+    run(|loader| {
+        use crate::util::msm::Accumulator;
+
+        let points = (1..4)
+            .map(|scalar| {
+                let point = (G1Affine::generator() * Fr::from(scalar)).into();
+                loader.assign_ec_point(Value::known(point))
+            })
+            .collect_vec();
+        let scalars = (5..8)
+            .map(|scalar| loader.assign_scalar(Value::known(Fr::from(scalar))))
+            .collect_vec();
+
+        let empty = Accumulator::<G1Affine, Rc<Halo2Loader>>::new(loader);
+        assert!(empty.finalize().is_identity_const());
+
+        let mut accumulator = Accumulator::<G1Affine, Rc<Halo2Loader>>::new(loader);
+        for (scalar, point) in scalars.iter().zip(points.iter()) {
+            accumulator.push(scalar.clone(), point.clone());
+        }
+        accumulator.push(scalars[0].clone(), points[0].clone());
+        assert_eq!(accumulator.len(), 3);
+        let accumulated = accumulator.finalize();
+
+        let expected = EcPoint::multi_scalar_multiplication([
+            (scalars[0].clone() + &scalars[0], points[0].clone()),
+            (scalars[1].clone(), points[1].clone()),
+            (scalars[2].clone(), points[2].clone()),
+        ]);
+        loader
+            .ec_point_assert_eq("", &accumulated, &expected)
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_msm_deterministic_order() {
+    run(|loader| {
+        let generator = G1Affine::generator();
+        let pairs = (1..5)
+            .map(|value| {
+                let point = (generator * Fr::from(value)).into();
+                (
+                    loader.assign_scalar(Value::known(Fr::from(value + 10))),
+                    loader.assign_ec_point(Value::known(point)),
+                )
+            })
+            .collect_vec();
+        let one = loader.load_one();
+        let pairs = iter::once((one, pairs[0].1.clone()))
+            .chain(pairs)
+            .collect_vec();
+
+        let offset = loader.ctx().offset();
+        let forward = loader
+            .try_multi_scalar_multiplication(pairs.clone())
+            .unwrap();
+        let forward_rows = loader.ctx().offset() - offset;
+
+        let offset = loader.ctx().offset();
+        let reversed = loader
+            .try_multi_scalar_multiplication(pairs.into_iter().rev().collect())
+            .unwrap();
+        assert_eq!(loader.ctx().offset() - offset, forward_rows);
+
+        loader.ec_point_assert_eq("", &forward, &reversed).unwrap();
+    });
+}
+
+#[test]
+fn test_assert_non_zero() {
+    run(|loader| {
+        let [zero, three] = [0, 3].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        loader.assert_non_zero("", &three).unwrap();
+        loader
+            .assert_non_zero("", &loader.load_const(&Fr::from(3)))
+            .unwrap();
+        assert!(loader.assert_non_zero("", &zero).is_err());
+        assert!(loader.assert_non_zero("", &loader.load_zero()).is_err());
+    });
+
+    assert!(NativeLoader.assert_non_zero("", &Fr::from(3)).is_ok());
+    assert!(NativeLoader.assert_non_zero("", &Fr::zero()).is_err());
+}
+
+#[test]
+fn test_snapshot() {
+    run(|loader| {
+        fn intermediates<'a>(loader: &Rc<Halo2Loader<'a>>) -> Vec<Scalar<'a>> {
+            let a = loader.assign_scalar(Value::known(Fr::from(3)));
+            let b = loader.load_const(&Fr::from(5));
+            let c = a.clone() * &b + &a;
+            let d = c.invert().unwrap();
+            vec![a, b, c, d]
+        }
+
+        let first = intermediates(loader);
+        let second = intermediates(loader);
+        assert_eq!(first[2].snapshot(), Some(Fr::from(18)));
+
+        let first = first.iter().collect::<loader::halo2::Snapshot>();
+        let second = second.iter().collect::<loader::halo2::Snapshot>();
+        assert_eq!(first.len(), 4);
+        assert_eq!(first, second);
+
+        #[cfg(feature = "serde")]
+        {
+            let serialized = serde_json::to_string(&first).unwrap();
+            assert_eq!(serialized, serde_json::to_string(&second).unwrap());
+            let deserialized =
+                serde_json::from_str::<loader::halo2::Snapshot>(&serialized).unwrap();
+            assert_eq!(deserialized, first);
+        }
+    });
+}
+
+#[test]
+fn test_mul_by_cofactor() {
+    run(|loader| {
+        let point = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        let offset = loader.ctx().offset();
+        let cleared = point.mul_by_cofactor();
+        assert_eq!(cleared.index(), point.index());
+        assert_eq!(loader.ctx().offset(), offset);
+    });
+
+    // BN254 has no small subgroup, so the cofactor path is exercised by
+    // multiplying with a small constant directly
+    run(|loader| {
+        let point = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        let expected =
+            loader.assign_ec_point(Value::known((G1Affine::generator() * Fr::from(4)).into()));
+        let multiplied = point.mul_by_small_constant(4);
+        loader
+            .ec_point_assert_eq("", &multiplied, &expected)
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_constant_registry() {
+    run(|loader| {
+        let generator = G1Affine::generator();
+        let doubled = (generator * Fr::from(2)).into();
+        let registry = [generator, doubled, G1Affine::identity(), generator]
+            .into_iter()
+            .collect::<loader::halo2::ConstantRegistry<_>>();
+        assert_eq!(registry.len(), 2);
+
+        loader.load_registry(&registry);
+        assert_eq!(loader.num_const_ec_points(), 2);
+
+        let offset = loader.ctx().offset();
+        let num_ec_points = loader.num_ec_points();
+        for point in [generator, doubled] {
+            loader.ec_point_load_const(&point);
+        }
+        assert_eq!(loader.ctx().offset(), offset);
+        assert_eq!(loader.num_ec_points(), num_ec_points);
+    });
+}
+
+#[test]
+fn test_reduce() {
+    run(|loader| {
+        let constant = loader.load_const(&Fr::from(3));
+        assert_eq!(constant.reduce().index(), constant.index());
+
+        // Same value reached through a sum and through a product
+        let [a, b] = [2, 3].map(|value| loader.assign_scalar(Value::known(Fr::from(value))));
+        let lhs = a.clone() + &b + &a;
+        let rhs = a.clone() * &b + &loader.load_one();
+        let (lhs, rhs) = (lhs.reduce(), rhs.reduce());
+        lhs.to_native()
+            .zip(rhs.to_native())
+            .assert_if_known(|(lhs, rhs)| lhs == rhs);
+        loader.assert_eq("", &lhs, &rhs).unwrap();
+    });
+}
+
+#[test]
+fn test_assign_ec_points() {
+    run(|loader| {
+        let points = (1..=16)
+            .map(|idx| (G1Affine::generator() * Fr::from(idx)).into())
+            .collect_vec();
+
+        let offset = loader.ctx().offset();
+        loader.assign_ec_point(Value::known(points[0]));
+        let single_cost = loader.ctx().offset() - offset;
+
+        let offset = loader.ctx().offset();
+        let assigned = loader.assign_ec_points(points.iter().copied().map(Value::known));
+        let batch_cost = loader.ctx().offset() - offset;
+        assert!(batch_cost <= 16 * single_cost);
+
+        assert_eq!(assigned.len(), 16);
+        for (assigned, point) in assigned.iter().zip(points) {
+            let expected = loader.ec_point_load_const(&point);
+            loader.ec_point_assert_eq("", assigned, &expected).unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_affine() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(5)));
+        let (a, b) = (Fr::from(3), Fr::from(7));
+
+        let offset = loader.ctx().offset();
+        let manual = loader.sum_with_coeff_and_const(&[(a, &x)], b);
+        let manual_cost = loader.ctx().offset() - offset;
+
+        let offset = loader.ctx().offset();
+        let affine = loader.affine(a, &x, b);
+        assert_eq!(loader.ctx().offset() - offset, manual_cost);
+
+        affine
+            .to_native()
+            .zip(manual.to_native())
+            .assert_if_known(|(affine, manual)| affine == manual && *affine == Fr::from(22));
+    });
+    assert_eq!(
+        NativeLoader.affine(Fr::from(3), &Fr::from(5), Fr::from(7)),
+        Fr::from(22)
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_assert_all_constrained() {
+    run(|loader| {
+        let dangling = loader.assign_scalar(Value::known(Fr::from(2)));
+        let used = loader.assign_scalar(Value::known(Fr::from(3)));
+        let point = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        loader
+            .assert_eq(
+                "",
+                &(used.clone() + &used),
+                &loader.load_const(&Fr::from(6)),
+            )
+            .unwrap();
+        loader.ec_point_assert_eq("", &point, &point).unwrap();
+
+        let err = loader.assert_all_constrained().unwrap_err();
+        assert!(
+            matches!(&err, crate::Error::AssertionFailure(message) if message.contains(&format!("{{{}}}", dangling.index())))
+        );
+
+        loader
+            .assert_zero("", &(dangling - &loader.load_const(&Fr::from(2))))
+            .unwrap();
+        loader.assert_all_constrained().unwrap();
+    });
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_assert_all_constrained_public_outputs() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(2)));
+        x.expose_public(3).unwrap();
+
+        let err = loader.assert_all_constrained().unwrap_err();
+        assert!(matches!(&err, crate::Error::AssertionFailure(message) if message.contains("[3]")));
+
+        assert_eq!(loader.take_public_outputs().len(), 1);
+        loader.assert_all_constrained().unwrap();
+    });
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_assert_all_constrained_short_circuit() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(2)));
+        let _ = x.clone() + &loader.load_zero();
+        let _ = x.clone() * &loader.load_one();
+        let _ = loader.sum_with_coeff(&[(Fr::one(), &x)]);
+        let _ = x.assigned();
+
+        let err = loader.assert_all_constrained().unwrap_err();
+        assert!(
+            matches!(&err, crate::Error::AssertionFailure(message) if message.contains(&format!("{{{}}}", x.index())))
+        );
+    });
+}
+
+#[test]
+fn test_load_const_repr() {
+    use crate::util::arithmetic::fe_from_big;
+    use loader::halo2::ReprForm;
+    use num_bigint::BigUint;
+
+    run(|loader| {
+        let value = Fr::from(5);
+        let canonical = value.to_repr();
+        let montgomery = {
+            let r = fe_from_big::<Fr>((BigUint::from(1u64) << 256) % modulus::<Fr>());
+            (value * r).to_repr()
+        };
+
+        let lhs = loader
+            .load_const_repr(canonical.as_ref(), ReprForm::Canonical)
+            .unwrap();
+        let rhs = loader
+            .load_const_repr(montgomery.as_ref(), ReprForm::Montgomery)
+            .unwrap();
+        lhs.to_native()
+            .zip(rhs.to_native())
+            .assert_if_known(|(lhs, rhs)| lhs == rhs && *lhs == value);
+
+        assert!(matches!(
+            loader.load_const_repr(&canonical.as_ref()[1..], ReprForm::Canonical),
+            Err(crate::Error::Parse(_))
+        ));
+        assert!(matches!(
+            loader.load_const_repr(&[0xff; 32], ReprForm::Montgomery),
+            Err(crate::Error::Parse(_))
+        ));
+    });
+}
+
+#[test]
+fn test_msm_strategy() {
+    use loader::halo2::{DefaultMsmStrategy, MsmStrategy};
+    use std::cell::Cell;
+
+    #[derive(Debug)]
+    struct CountingStrategy(Rc<Cell<usize>>);
+
+    impl<'a> MsmStrategy<'a, G1Affine, BaseFieldEccChip> for CountingStrategy {
+        fn msm(
+            &self,
+            loader: &Rc<Halo2Loader<'a>>,
+            pairs: Vec<(Scalar<'a>, EcPoint<'a>)>,
+        ) -> Result<EcPoint<'a>, crate::Error> {
+            self.0.set(self.0.get() + 1);
+            DefaultMsmStrategy.msm(loader, pairs)
+        }
+    }
+
+    run(|loader| {
+        let pairs = [(2, 3), (5, 7)]
+            .map(|(scalar, point)| {
+                (
+                    loader.assign_scalar(Value::known(Fr::from(scalar))),
+                    loader.assign_ec_point(Value::known(
+                        (G1Affine::generator() * Fr::from(point)).into(),
+                    )),
+                )
+            })
+            .to_vec();
+
+        let invoked = Rc::new(Cell::new(0));
+        loader.set_msm_strategy(Rc::new(CountingStrategy(invoked.clone())));
+        let custom = EcPoint::multi_scalar_multiplication(pairs.clone());
+        assert_eq!(invoked.get(), 1);
+
+        loader.set_msm_strategy(Rc::new(DefaultMsmStrategy));
+        let default = EcPoint::multi_scalar_multiplication(pairs);
+        assert_eq!(invoked.get(), 1);
+        loader.ec_point_assert_eq("", &custom, &default).unwrap();
+    });
+}
+
+#[test]
+fn test_hash_by_index() {
+    use std::collections::HashSet;
+
+    run(|loader| {
+        let a = loader.assign_scalar(Value::known(Fr::from(2)));
+        let b = loader.assign_scalar(Value::known(Fr::from(2)));
+        let scalars = [a.clone(), b, a].into_iter().collect::<HashSet<_>>();
+        assert_eq!(scalars.len(), 2);
+
+        let p = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        let q = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        let ec_points = [p.clone(), q, p].into_iter().collect::<HashSet<_>>();
+        assert_eq!(ec_points.len(), 2);
+    });
+}
+
+#[test]
+fn test_scalar_to_base() {
+    run(|loader| {
+        // BN254's scalar field is smaller than its base field, so every scalar
+        // fits in
+        for value in [Fr::from(5), -Fr::one()] {
+            let scalar = loader.assign_scalar(Value::known(value));
+            let base = loader.scalar_to_base(&scalar).unwrap();
+            base.native()
+                .value()
+                .assert_if_known(|native| **native == value);
+        }
+        assert!(loader
+            .scalar_to_base(&loader.load_const(&Fr::from(7)))
+            .is_ok());
+    });
+}
+
+#[test]
+fn test_row_metering_accumulates() {
+    use crate::loader::Loader;
+
+    run(|loader| {
+        const SQUARE: &str = "square";
+
+        let x = loader.assign_scalar(Value::known(Fr::from(3)));
+        let mut costs = Vec::new();
+        for _ in 0..2 {
+            let offset = loader.ctx().offset();
+            loader.start_cost_metering(SQUARE);
+            let _ = x.clone() * &x;
+            loader.end_cost_metering();
+            costs.push(loader.ctx().offset() - offset);
+        }
+        let other = String::from("other");
+        loader.start_cost_metering(&other);
+        loader.end_cost_metering();
+
+        assert_eq!(loader.row_metering(SQUARE), Some(costs.iter().sum()));
+        assert_eq!(loader.row_metering("other"), Some(0));
+    });
+}
+
+#[test]
+fn test_assert_eq_many() {
+    fn points<'a>(loader: &Rc<Halo2Loader<'a>>, scalars: &[u64]) -> Vec<EcPoint<'a>> {
+        scalars
+            .iter()
+            .map(|scalar| {
+                loader.assign_ec_point(Value::known(
+                    (G1Affine::generator() * Fr::from(*scalar)).into(),
+                ))
+            })
+            .collect()
+    }
+
+    run(|loader| {
+        let lhs = points(loader, &[1, 2, 3]);
+        let rhs = points(loader, &[1, 2, 3]);
+        loader.assert_eq_many("", &lhs, &rhs).unwrap();
+        assert!(matches!(
+            loader.assert_eq_many("", &lhs, &rhs[..2]),
+            Err(crate::Error::InvalidInput { detail, .. }) if detail.contains("Length")
+        ));
+    });
+
+    let prover = mock(|loader| {
+        let lhs = points(loader, &[1, 2, 3]);
+        let rhs = points(loader, &[1, 5, 3]);
+        assert!(matches!(
+            loader.assert_eq_many("", &lhs, &rhs),
+            Err(crate::Error::AssertionFailureDetailed { detail, .. }) if detail == "Mismatch at index 1"
+        ));
+    });
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_chained_msm_unnormalized() {
+    run(|loader| {
+        let [p, q, r] = [2, 3, 5].map(|scalar| {
+            loader.assign_ec_point(Value::known(
+                (G1Affine::generator() * Fr::from(scalar)).into(),
+            ))
+        });
+        let two = loader.assign_scalar(Value::known(Fr::from(2)));
+
+        let intermediate =
+            EcPoint::multi_scalar_multiplication([(two.clone(), p), (loader.load_one(), q)]);
+        assert!(!intermediate.is_normalized());
+        let output = EcPoint::multi_scalar_multiplication([
+            (loader.load_one(), intermediate.clone()),
+            (two, r),
+        ]);
+        assert!(!intermediate.is_normalized());
+
+        let expected = loader.ec_point_load_const(&(G1Affine::generator() * Fr::from(17)).into());
+        loader.ec_point_assert_eq("", &output, &expected).unwrap();
+    });
+}
+
+#[test]
+fn test_hash_to_scalar() {
+    use crate::util::hash::Poseidon;
+
+    run(|loader| {
+        let values = [1, 2, 3, 4, 5].map(Fr::from);
+        let expected = {
+            let mut hasher = Poseidon::<Fr, Fr, 5, 4>::new(NativeLoader, 8, 60);
+            hasher.update(&values);
+            hasher.squeeze()
+        };
+
+        let inputs = values.map(|value| loader.assign_scalar(Value::known(value)));
+        loader
+            .hash_to_scalar::<5, 4>(8, 60, &inputs)
+            .to_native()
+            .assert_if_known(|hash| *hash == expected);
+    });
+}
+
+#[test]
+fn test_expose_public() {
+    fn expose(loader: &Rc<Halo2Loader>) {
+        let x = loader.assign_scalar(Value::known(Fr::from(3)));
+        (x.clone() * &x).expose_public(1).unwrap();
+        loader.load_const(&Fr::from(5)).expose_public(0).unwrap();
+        assert!(x.expose_public(1).is_err());
+    }
+
+    mock_with_instances(expose, vec![Fr::from(5), Fr::from(9)]).assert_satisfied();
+    assert!(mock_with_instances(expose, vec![Fr::from(5), Fr::from(8)])
+        .verify()
+        .is_err());
+}
+
+#[test]
+fn test_chunked_msm() {
+    run(|loader| {
+        let pairs = (1..=5)
+            .map(|idx| {
+                (
+                    loader.assign_scalar(Value::known(Fr::from(idx + 1))),
+                    loader.assign_ec_point(Value::known(
+                        (G1Affine::generator() * Fr::from(idx)).into(),
+                    )),
+                )
+            })
+            .collect_vec();
+        let unchunked = EcPoint::multi_scalar_multiplication(pairs.clone());
+
+        loader.set_max_msm_terms(2);
+        let chunked = EcPoint::multi_scalar_multiplication(pairs);
+        loader.ec_point_assert_eq("", &chunked, &unchunked).unwrap();
+
+        // sum((i + 1) * i) for i in 1..=5
+        let expected = loader.ec_point_load_const(&(G1Affine::generator() * Fr::from(70)).into());
+        loader.ec_point_assert_eq("", &chunked, &expected).unwrap();
+    });
+}
+
+#[test]
+fn test_scalar_accumulator() {
+    use loader::halo2::ScalarAccumulator;
+
+    run(|loader| {
+        let terms = (0..50)
+            .map(|idx| loader.assign_scalar(Value::known(Fr::from(idx))))
+            .collect_vec();
+
+        let offset = loader.ctx().offset();
+        let naive = terms
+            .iter()
+            .fold(loader.load_const(&Fr::from(7)), |acc, term| acc + term);
+        let naive_cost = loader.ctx().offset() - offset;
+
+        let offset = loader.ctx().offset();
+        let mut acc = ScalarAccumulator::new(loader);
+        acc.add_const(Fr::from(3));
+        acc += loader.load_const(&Fr::from(4));
+        for term in terms.iter() {
+            acc += term;
+        }
+        let fused = acc.finalize();
+        assert!(loader.ctx().offset() - offset < naive_cost);
+
+        loader.assert_eq("", &fused, &naive).unwrap();
+        fused
+            .to_native()
+            .assert_if_known(|value| *value == Fr::from(1232));
+    });
+}
+
+#[test]
+fn test_can_add_incompletely() {
+    run(|loader| {
+        let generator = G1Affine::generator();
+        let p = loader.ec_point_load_const(&generator);
+        let q = loader.ec_point_load_const(&(generator * Fr::from(2)).into());
+        let neg_p = loader.ec_point_load_const(&(-generator));
+        let witness = loader.assign_ec_point(Value::known(generator));
+
+        assert!(p.can_add_incompletely(&q));
+        assert!(!p.can_add_incompletely(&p));
+        assert!(!p.can_add_incompletely(&neg_p));
+        assert!(!p.can_add_incompletely(&witness));
+        assert!(!p.can_add_incompletely(&loader.ec_point_identity()));
+
+        // Points assigned in a batch are indexed as constants as well
+        let batch = loader.assign_const_ec_points(&[(generator * Fr::from(3)).into()]);
+        assert!(batch[0].can_add_incompletely(&p));
+        assert!(batch[0].can_add_incompletely(&q));
+    });
+}
+
+#[test]
+fn test_with_capacity() {
+    #[derive(Clone)]
+    struct WithCapacityCircuit;
+
+    impl Circuit<Fr> for WithCapacityCircuit {
+        type Config = MainGateWithRangeConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut plonk::ConstraintSystem<Fr>) -> Self::Config {
+            LoaderCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), plonk::Error> {
+            config.range_chip().load_table(&mut layouter)?;
+            layouter.assign_region(
+                || "",
+                |region| {
+                    let ctx = RegionCtx::new(region, 0);
+                    let hint = CapacityHint {
+                        num_scalars: 16,
+                        num_ec_points: 4,
+                        num_meterings: 0,
+                    };
+                    let loader = Halo2Loader::with_capacity(config.ecc_chip(), ctx, hint);
+                    let capacities = loader.capacities();
+                    assert!(capacities.0 >= 16 && capacities.1 >= 4);
+
+                    for value in 0..16 {
+                        loader.assign_scalar(Value::known(Fr::from(value)));
+                    }
+                    for _ in 0..4 {
+                        loader.assign_ec_point(Value::known(G1Affine::generator()));
+                    }
+                    // Nothing got reallocated
+                    assert_eq!(loader.capacities(), capacities);
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    MockProver::run(K, &WithCapacityCircuit, vec![Vec::new()])
+        .unwrap()
+        .assert_satisfied();
+}
+
+#[test]
+fn test_new_at_fresh_region() {
+    #[derive(Clone)]
+    struct FreshRegionCircuit;
+
+    impl Circuit<Fr> for FreshRegionCircuit {
+        type Config = MainGateWithRangeConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut plonk::ConstraintSystem<Fr>) -> Self::Config {
+            LoaderCircuit::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), plonk::Error> {
+            layouter.assign_region(
+                || "",
+                |region| {
+                    let mut ctx = RegionCtx::new(region, 0);
+                    ctx.next();
+                    assert!(matches!(
+                        Halo2Loader::new_at_fresh_region(config.ecc_chip(), ctx),
+                        Err(crate::Error::InvalidInput {
+                            op: "new_at_offset",
+                            ..
+                        })
+                    ));
+                    Ok(())
+                },
+            )?;
+            layouter.assign_region(
+                || "",
+                |region| {
+                    let mut ctx = RegionCtx::new(region, 0);
+                    ctx.next();
+                    assert!(Halo2Loader::new_at_offset(config.ecc_chip(), ctx, 1).is_ok());
+                    Ok(())
+                },
+            )?;
+            layouter.assign_region(
+                || "",
+                |region| {
+                    let ctx = RegionCtx::new(region, 0);
+                    assert!(Halo2Loader::new_at_fresh_region(config.ecc_chip(), ctx).is_ok());
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    MockProver::run(K, &FreshRegionCircuit, vec![Vec::new()])
+        .unwrap()
+        .assert_satisfied();
+}
+
+#[test]
+fn test_lagrange_interpolate() {
+    fn f(t: u64) -> Fr {
+        Fr::from(2 * t * t + 3 * t + 1)
+    }
+
+    run(|loader| {
+        let points = [1, 2, 4]
+            .map(|t| {
+                (
+                    loader.assign_scalar(Value::known(Fr::from(t))),
+                    loader.assign_scalar(Value::known(f(t))),
+                )
+            })
+            .to_vec();
+        let x = loader.assign_scalar(Value::known(Fr::from(5)));
+        loader
+            .lagrange_interpolate(&points, &x)
+            .unwrap()
+            .to_native()
+            .assert_if_known(|value| *value == f(5));
+
+        let duplicated = [points[0].clone(), points[1].clone(), points[0].clone()];
+        assert!(loader.lagrange_interpolate(&duplicated, &x).is_err());
+
+        let constants = [1, 1].map(|t| (loader.load_const(&Fr::from(t)), loader.load_const(&f(t))));
+        assert!(loader.lagrange_interpolate(&constants, &x).is_err());
+    });
+
+    let points = [1, 2, 4].map(|t| (Fr::from(t), f(t)));
+    assert_eq!(
+        NativeLoader
+            .lagrange_interpolate(&points, &Fr::from(5))
+            .unwrap(),
+        f(5)
+    );
+    assert!(NativeLoader
+        .lagrange_interpolate(&[points[0], points[0]], &Fr::from(5))
+        .is_err());
+}
+
+#[test]
+fn test_lagrange_interpolate_duplicate_value() {
+    let prover = mock(|loader| {
+        let points = [1, 2, 1]
+            .map(|t| {
+                (
+                    loader.assign_scalar(Value::known(Fr::from(t))),
+                    loader.assign_scalar(Value::known(Fr::from(t))),
+                )
+            })
+            .to_vec();
+        let x = loader.assign_scalar(Value::known(Fr::from(5)));
+        assert!(loader.lagrange_interpolate(&points, &x).is_err());
+    });
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_empty_terms() {
+    run(|loader| {
+        use crate::util::msm::Msm;
+
+        let offset = loader.ctx().offset();
+        let msm = loader.try_multi_scalar_multiplication(Vec::new()).unwrap();
+        assert!(msm.is_identity_const());
+
+        // Through `Msm` as the verifier does, left without any term
+        let (msm, _) = Msm::<G1Affine, Rc<Halo2Loader>>::constant(loader.load_one()).split();
+        assert!(msm.evaluate(None).is_identity_const());
+
+        let constant = Fr::from(3);
+        for sum in [
+            loader.sum_with_coeff_and_const(&[], constant),
+            loader.sum_products_with_coeff_and_const(&[], constant),
+        ] {
+            sum.to_native().assert_if_known(|value| *value == constant);
+        }
+        loader
+            .sum(&[])
+            .to_native()
+            .assert_if_known(|value| *value == Fr::zero());
+        assert_eq!(loader.ctx().offset(), offset);
+    });
+
+    assert_eq!(
+        G1Affine::multi_scalar_multiplication(iter::empty()),
+        G1Affine::identity()
+    );
+    assert_eq!(
+        NativeLoader.sum_products_with_coeff_and_const(&[], Fr::from(3)),
+        Fr::from(3)
+    );
+}
+
+#[test]
+fn test_assign_evaluations() {
+    run(|loader| {
+        let values = [1, 2, 3, 4].map(Fr::from);
+        let offset = loader.ctx().offset();
+        let evals = loader.assign_evaluations(&values.map(Value::known));
+        assert_eq!(
+            loader.row_metering("evaluations"),
+            Some(loader.ctx().offset() - offset)
+        );
+
+        assert_eq!(evals.len(), 4);
+        for (eval, value) in evals.iter().zip(values) {
+            eval.to_native().assert_if_known(|eval| *eval == value);
+        }
+    });
+}
+
+#[test]
+fn test_into_native_cell() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(3)));
+        let cell = x.into_native_cell().unwrap();
+        cell.value().assert_if_known(|value| **value == Fr::from(3));
+
+        // Round trip through a cell wrapped back into the loader
+        let y = loader.scalar_from_instance(cell);
+        loader.assert_eq("", &x, &y).unwrap();
+
+        let constant = loader.load_const(&Fr::from(5)).into_native_cell().unwrap();
+        constant
+            .value()
+            .assert_if_known(|value| **value == Fr::from(5));
+    });
+}
+
+#[cfg(feature = "symbol_table")]
+#[test]
+fn test_symbol_table() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(3)));
+        let y = x.clone() * &x;
+        loader.bind("x", &x);
+        loader.bind("x_squared", &y);
+
+        let bound = loader.get_scalar("x_squared").unwrap();
+        assert_eq!(bound.index(), y.index());
+        loader
+            .assert_eq("", &bound, &loader.load_const(&Fr::from(9)))
+            .unwrap();
+        assert!(loader.get_scalar("z").is_none());
+
+        loader.bind("x", &y);
+        assert_eq!(loader.get_scalar("x").unwrap().index(), y.index());
+    });
+}
+
+#[test]
+fn test_breadcrumbs() {
+    run(|loader| {
+        loader.enable_breadcrumbs();
+        let x = loader.assign_scalar(Value::known(Fr::from(3)));
+        let generator = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        loader
+            .assert_eq("x", &x, &loader.load_const(&Fr::from(3)))
+            .unwrap();
+        loader
+            .ec_point_assert_eq("generator", &generator, &generator)
+            .unwrap();
+
+        let err = loader
+            .assert_one("three is one", &loader.load_const(&Fr::from(3)))
+            .unwrap_err();
+        match err {
+            crate::Error::AssertionFailureDetailed { annotation, detail } => {
+                assert_eq!(annotation, "three is one");
+                assert!(detail.ends_with(r#"after successfully checking: ["x", "generator"]"#));
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+        assert_eq!(loader.take_breadcrumbs(), ["x", "generator"]);
+    });
+}
+
+#[test]
+fn test_recursion_limit() {
+    fn nest(loader: &Rc<Halo2Loader>, depth: usize) -> Result<usize, crate::Error> {
+        if depth == 0 {
+            return Ok(0);
+        }
+        loader.recurse(|| nest(loader, depth - 1).map(|nested| nested + 1))
+    }
+
+    run(|loader| {
+        loader.set_max_depth(2);
+        assert_eq!(nest(loader, 2).unwrap(), 2);
+        assert!(matches!(
+            nest(loader, 3),
+            Err(crate::Error::RecursionLimit(2))
+        ));
+        // Depth is restored after the failure
+        assert_eq!(nest(loader, 1).unwrap(), 1);
+    });
+}
+
+#[test]
+fn test_ipa_fold() {
+    run(|loader| {
+        let generators = (1..9)
+            .map(|scalar| (G1Affine::generator() * Fr::from(scalar)).into())
+            .collect_vec();
+        let challenges = [2, 3, 5].map(Fr::from);
+        let expected = generators
+            .iter()
+            .enumerate()
+            .map(|(idx, generator)| {
+                let coeff = challenges
+                    .iter()
+                    .enumerate()
+                    .map(|(j, challenge)| {
+                        if idx >> (challenges.len() - 1 - j) & 1 == 1 {
+                            *challenge
+                        } else {
+                            Field::invert(challenge).unwrap()
+                        }
+                    })
+                    .fold(Fr::one(), |acc, challenge| acc * challenge);
+                *generator * coeff
+            })
+            .reduce(|acc, term| acc + term)
+            .unwrap()
+            .into();
+
+        let native = NativeLoader.ipa_fold(&generators, &challenges).unwrap();
+        assert_eq!(native, expected);
+        assert!(NativeLoader
+            .ipa_fold(&generators[..4], &challenges)
+            .is_err());
+
+        let folded = loader
+            .ipa_fold(
+                &generators
+                    .iter()
+                    .map(|generator| loader.assign_ec_point(Value::known(*generator)))
+                    .collect_vec(),
+                &challenges
+                    .iter()
+                    .map(|challenge| loader.assign_scalar(Value::known(*challenge)))
+                    .collect_vec(),
+            )
+            .unwrap();
+        loader
+            .ec_point_assert_eq("", &folded, &loader.ec_point_load_const(&expected))
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_pow5() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(3)));
+
+        let offset = loader.ctx().offset();
+        let pow5 = loader.pow5(&x);
+        let pow5_rows = loader.ctx().offset() - offset;
+
+        let offset = loader.ctx().offset();
+        let naive = x.clone() * &x * &x * &x * &x;
+        let naive_rows = loader.ctx().offset() - offset;
+
+        assert!(pow5_rows < naive_rows);
+        loader.assert_eq("", &pow5, &naive).unwrap();
+        loader
+            .assert_eq("", &pow5, &loader.load_const(&Fr::from(243)))
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_limbs() {
+    run(|loader| {
+        let value = -Fr::from(3);
+        let bits_per_limb = loader.scalar_limb_info().bits_per_limb;
+        let recompose = |limbs: Vec<Value<Fr>>| {
+            limbs
+                .into_iter()
+                .rev()
+                .fold(Value::known(Fr::zero()), |acc, limb| {
+                    acc.zip(limb).map(|(acc, limb)| {
+                        acc * Fr::from(2).pow_vartime([bits_per_limb as u64]) + limb
+                    })
+                })
+        };
+
+        let assigned = loader.assign_scalar(Value::known(value));
+        let constant = loader.load_const(&value);
+        assert_eq!(constant.limbs().len(), loader.scalar_limb_info().num_limbs);
+        recompose(assigned.limbs()).assert_if_known(|recomposed| *recomposed == value);
+        recompose(constant.limbs()).assert_if_known(|recomposed| *recomposed == value);
+    });
+}
+
+#[test]
+fn test_combine_accumulators() {
+    run(|loader| {
+        let point = |scalar: u64| -> G1Affine { (G1Affine::generator() * Fr::from(scalar)).into() };
+        let accumulators = [(point(2), point(3)), (point(5), point(7))];
+        let r = Fr::from(11);
+
+        let (lhs, rhs) = NativeLoader.combine_accumulators(&accumulators, &r);
+        assert_eq!(lhs, (accumulators[0].0 * r + accumulators[1].0).into());
+        assert_eq!(rhs, (accumulators[0].1 * r + accumulators[1].1).into());
+
+        let assigned = accumulators
+            .iter()
+            .map(|(lhs, rhs)| {
+                (
+                    loader.assign_ec_point(Value::known(*lhs)),
+                    loader.assign_ec_point(Value::known(*rhs)),
+                )
+            })
+            .collect_vec();
+        let r = loader.assign_scalar(Value::known(r));
+        let (combined_lhs, combined_rhs) = loader.combine_accumulators(&assigned, &r);
+        loader
+            .ec_point_assert_eq("", &combined_lhs, &loader.ec_point_load_const(&lhs))
+            .unwrap();
+        loader
+            .ec_point_assert_eq("", &combined_rhs, &loader.ec_point_load_const(&rhs))
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_msm_with_constant_scalars() {
+    run(|loader| {
+        let points = (1..=4)
+            .map(|idx| {
+                loader.assign_ec_point(Value::known((G1Affine::generator() * Fr::from(idx)).into()))
+            })
+            .collect_vec();
+        let scalars = [2, 3, 5, 7].map(Fr::from);
+
+        let variable = EcPoint::multi_scalar_multiplication(
+            scalars
+                .iter()
+                .map(|scalar| loader.assign_scalar(Value::known(*scalar)))
+                .zip(points.iter().cloned()),
+        );
+        let mixed = EcPoint::multi_scalar_multiplication(
+            scalars
+                .iter()
+                .enumerate()
+                .map(|(idx, scalar)| {
+                    if idx % 2 == 0 {
+                        loader.load_const(scalar)
+                    } else {
+                        loader.assign_scalar(Value::known(*scalar))
+                    }
+                })
+                .zip(points.iter().cloned()),
+        );
+        loader.ec_point_assert_eq("", &mixed, &variable).unwrap();
+
+        // 2 * 1 + 3 * 2 + 5 * 3 + 7 * 4
+        let expected = loader.ec_point_load_const(&(G1Affine::generator() * Fr::from(51)).into());
+        loader.ec_point_assert_eq("", &mixed, &expected).unwrap();
+    });
+}
+
+#[test]
+fn test_assert_not_identity() {
+    run(|loader| {
+        let generator = loader.assign_ec_point(Value::known(G1Affine::generator()));
+        generator.assert_not_identity("generator").unwrap();
+
+        let identity = loader.ec_point_load_zero();
+        assert!(matches!(
+            identity.assert_not_identity("identity"),
+            Err(crate::Error::AssertionFailure(annotation)) if annotation == "identity"
+        ));
+    });
+
+    // Identity encoded as `(0, 0)`, which is off curve and bypasses
+    // `assign_point`, is rejected by the constraints
+    let prover = mock(|loader| {
+        use crate::loader::halo2::EccInstructions;
+        use halo2_wrong_ecc::{
+            integer::{Integer, IntegerInstructions as _, Range},
+            AssignedPoint,
+        };
+
+        let ecc_chip = loader.ecc_chip();
+        let mut ctx = loader.ctx_mut();
+        let rns = ecc_chip.integer_chip().rns();
+        let [x, y] = [(); 2].map(|_| {
+            let zero = Integer::from_fe(Fq::zero(), rns.clone());
+            ecc_chip
+                .integer_chip()
+                .assign_integer(&mut ctx, Value::known(zero).into(), Range::Remainder)
+                .unwrap()
+        });
+        let identity = AssignedPoint::new(x, y);
+        let _ = EccInstructions::assert_not_identity(&*ecc_chip, &mut ctx, &identity);
+    });
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_load_srs() {
+    run(|loader| {
+        let srs = (1..5)
+            .map(|scalar| (G1Affine::generator() * Fr::from(scalar)).into())
+            .collect_vec();
+        let loaded = loader.load_srs(&srs, 4).unwrap();
+        assert_eq!(loaded.len(), 4);
+        assert_eq!(loader.num_const_ec_points(), 4);
+
+        assert!(matches!(
+            loader.load_srs(&srs, 8),
+            Err(crate::Error::InvalidInput { detail, .. })
+                if detail == "Expected 8 points but got 4"
+        ));
+
+        let mut invalid = srs;
+        invalid[2] = G1Affine {
+            x: Fq::one(),
+            y: Fq::one(),
+        };
+        assert!(matches!(
+            loader.load_srs(&invalid, 4),
+            Err(crate::Error::InvalidInput { detail, .. })
+                if detail == "Point 2 is not on curve"
+        ));
+    });
+}
+
+#[test]
+fn test_repeated_square() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(3)));
+        assert_eq!(loader.repeated_square(&x, 0).index(), x.index());
+        loader
+            .assert_eq("", &loader.repeated_square(&x, 3), &x.pow_const(8))
+            .unwrap();
+
+        // Constants are folded without assigning anything
+        let offset = loader.ctx().offset();
+        let squared = loader.repeated_square(&loader.load_const(&Fr::from(3)), 3);
+        assert_eq!(loader.ctx().offset(), offset);
+        loader
+            .assert_eq("", &squared, &loader.load_const(&Fr::from(6561)))
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_scalar_from_limbs() {
+    run(|loader| {
+        let x = loader.assign_scalar(Value::known(Fr::from(3)));
+        let cell = x.into_native_cell().unwrap();
+
+        let y = loader.scalar_from_limbs(vec![cell.clone()]).unwrap();
+        for (x, y) in x.limbs().into_iter().zip(y.limbs()) {
+            x.zip(y).assert_if_known(|(x, y)| x == y);
+        }
+        loader.assert_eq("", &x, &y).unwrap();
+
+        assert!(matches!(
+            loader.scalar_from_limbs(vec![cell.clone(), cell]),
+            Err(crate::Error::InvalidInput { detail, .. })
+                if detail == "Expected 1 limbs but got 2"
+        ));
+    });
+}
+
+#[test]
+fn test_msm_histogram() {
+    run(|loader| {
+        let terms = |n: u64| {
+            (1..=n)
+                .map(|idx| {
+                    (
+                        loader.assign_scalar(Value::known(Fr::from(idx))),
+                        loader.assign_ec_point(Value::known(
+                            (G1Affine::generator() * Fr::from(idx)).into(),
+                        )),
+                    )
+                })
+                .collect_vec()
+        };
+        let [two, ten] = [terms(2), terms(10)];
+        EcPoint::multi_scalar_multiplication(two.clone());
+        EcPoint::multi_scalar_multiplication(two);
+        EcPoint::multi_scalar_multiplication(ten);
+
+        let histogram = loader.msm_histogram();
+        assert_eq!(histogram.keys().copied().collect_vec(), [2, 10]);
+        let (count_two, rows_two) = histogram[&2];
+        let (count_ten, rows_ten) = histogram[&10];
+        assert_eq!((count_two, count_ten), (2, 1));
+        assert!(rows_ten > rows_two / 2);
+    });
+}
+
+#[test]
+fn test_kzg_accumulator_from_opening() {
+    use crate::pcs::{
+        kzg::{Gwc19, Kzg, KzgAccumulator, KzgDecidingKey},
+        Decider,
+    };
+    use halo2_curves::bn256::{Bn256, G2Affine};
+
+    run(|loader| {
+        // Opening `p(X) = a0 + a1 * X` at `z`, whose quotient is `a1`
+        let s = Fr::from(42);
+        let (a0, a1, z) = (Fr::from(3), Fr::from(5), Fr::from(7));
+        let g = G1Affine::generator();
+        let commitment: G1Affine = (g * (a0 + a1 * s)).into();
+        let v = a0 + a1 * z;
+        let proof: G1Affine = (g * a1).into();
+        let svk = g.into();
+        let dk =
+            KzgDecidingKey::<Bn256>::new(G2Affine::generator(), (G2Affine::generator() * s).into());
+
+        let native = KzgAccumulator::<G1Affine, NativeLoader>::from_opening(
+            &svk,
+            &commitment,
+            &z,
+            &v,
+            &proof,
+        );
+        assert!(
+            <Kzg<Bn256, Gwc19> as Decider<G1Affine, NativeLoader>>::decide(&dk, native.clone())
+        );
+        let wrong = KzgAccumulator::<G1Affine, NativeLoader>::from_opening(
+            &svk,
+            &commitment,
+            &z,
+            &(v + Fr::one()),
+            &proof,
+        );
+        assert!(!<Kzg<Bn256, Gwc19> as Decider<G1Affine, NativeLoader>>::decide(&dk, wrong));
+
+        let accumulator = KzgAccumulator::<G1Affine, Rc<Halo2Loader>>::from_opening(
+            &svk,
+            &loader.assign_ec_point(Value::known(commitment)),
+            &loader.assign_scalar(Value::known(z)),
+            &loader.assign_scalar(Value::known(v)),
+            &loader.assign_ec_point(Value::known(proof)),
+        );
+        loader
+            .ec_point_assert_eq(
+                "",
+                &accumulator.lhs,
+                &loader.ec_point_load_const(&native.lhs),
+            )
+            .unwrap();
+        loader
+            .ec_point_assert_eq(
+                "",
+                &accumulator.rhs,
+                &loader.ec_point_load_const(&native.rhs),
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_evaluate_loaded_expression() {
+    use crate::util::protocol::{CommonPolynomial, Expression, Query};
+    use std::collections::HashMap;
+
+    run(|loader| {
+        let [q_l, q_r, q_m, q_c, a, b] =
+            [0, 1, 2, 3, 4, 5].map(|poly| Expression::<Fr>::Polynomial(Query::new(poly, 0)));
+        let values = [2, 3, 5, 7, 11, 13].map(Fr::from);
+        let (theta, l_0) = (Fr::from(17), Fr::from(19));
+        // Standard PLONK gate and `l_0 * (a - 1)` combined by a challenge
+        let gate = q_l * a.clone() + q_r * b.clone() + q_m * a.clone() * b + q_c;
+        let boundary = Expression::CommonPolynomial(CommonPolynomial::Lagrange(0))
+            * (a - Expression::Constant(Fr::one()));
+        let expressions = [
+            gate.clone(),
+            gate * Expression::Challenge(0) + boundary * Fr::from(2),
+        ];
+        let [q_l, q_r, q_m, q_c, a, b] = values;
+        let expected = {
+            let gate = q_l * a + q_r * b + q_m * a * b + q_c;
+            [gate, gate * theta + l_0 * (a - Fr::one()) * Fr::from(2)]
+        };
+
+        let native_evaluations = values
+            .iter()
+            .enumerate()
+            .map(|(poly, value)| (Query::new(poly, 0), *value))
+            .collect::<HashMap<_, _>>();
+        let evaluations = native_evaluations
+            .iter()
+            .map(|(query, value)| (*query, loader.assign_scalar(Value::known(*value))))
+            .collect::<HashMap<_, _>>();
+        let challenges = [loader.assign_scalar(Value::known(theta))];
+        let l_0_loaded = loader.assign_scalar(Value::known(l_0));
+
+        for (expression, expected) in expressions.iter().zip(expected) {
+            let native = expression
+                .evaluate_loaded(&NativeLoader, &|_| l_0, &native_evaluations, &[theta])
+                .unwrap();
+            assert_eq!(native, expected);
+
+            let evaluated = expression
+                .evaluate_loaded(loader, &|_| l_0_loaded.clone(), &evaluations, &challenges)
+                .unwrap();
+            loader
+                .assert_eq("", &evaluated, &loader.load_const(&expected))
+                .unwrap();
+        }
+
+        assert!(matches!(
+            Expression::<Fr>::Challenge(1).evaluate_loaded(
+                loader,
+                &|_| l_0_loaded.clone(),
+                &evaluations,
+                &challenges
+            ),
+            Err(crate::Error::InvalidChallenge(1))
+        ));
+    });
+}
+
+#[test]
+fn test_scale() {
+    run(|loader| {
+        let xs = [2, 3, 5]
+            .map(|value| loader.assign_scalar(Value::known(Fr::from(value))))
+            .into_iter()
+            .chain(Some(loader.load_const(&Fr::from(7))))
+            .collect_vec();
+
+        for (scaled, expected) in loader.scale(Fr::from(3), &xs).iter().zip([6, 9, 15, 21]) {
+            loader
+                .assert_eq("", scaled, &loader.load_const(&Fr::from(expected)))
+                .unwrap();
+        }
+
+        let offset = loader.ctx().offset();
+        let unchanged = loader.scale(Fr::one(), &xs);
+        let zeros = loader.scale(Fr::zero(), &xs);
+        assert_eq!(loader.ctx().offset(), offset);
+        assert_eq!(unchanged, xs);
+        for zero in zeros {
+            loader.assert_zero("", &zero).unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_lagrange_first_and_last() {
+    use crate::util::arithmetic::{root_of_unity, Domain, Rotation};
+
+    run(|loader| {
+        let domain = Domain::<Fr>::new(4, root_of_unity(4));
+        let x = Fr::from(42);
+        // Product over all other rows of `(x - omega^j) / (omega^i - omega^j)`
+        let reference = |rotation: i32| {
+            let omega_i = domain.rotate_scalar(Fr::one(), Rotation(rotation));
+            (0..domain.n as i32)
+                .map(|j| domain.rotate_scalar(Fr::one(), Rotation(j)))
+                .filter(|omega_j| *omega_j != omega_i)
+                .fold(Fr::one(), |acc, omega_j| {
+                    acc * (x - omega_j) * (omega_i - omega_j).invert().unwrap()
+                })
+        };
+
+        assert_eq!(NativeLoader.lagrange_first(&domain, &x), reference(0));
+        assert_eq!(NativeLoader.lagrange_last(&domain, &x, 0), reference(-1));
+        assert_eq!(NativeLoader.lagrange_last(&domain, &x, 5), reference(-6));
+        // Last usable row is `n - blinding - 1`
+        assert_eq!(reference(-6), reference(domain.n as i32 - 6));
+
+        let assigned = loader.assign_scalar(Value::known(x));
+        for (evaluated, expected) in [
+            (loader.lagrange_first(&domain, &assigned), reference(0)),
+            (loader.lagrange_last(&domain, &assigned, 0), reference(-1)),
+            (loader.lagrange_last(&domain, &assigned, 5), reference(-6)),
+        ] {
+            loader
+                .assert_eq("", &evaluated, &loader.load_const(&expected))
+                .unwrap();
+        }
+    });
+}
+
+#[test]
+fn test_transcript_strict_mode() {
+    use crate::util::transcript::StrictMode;
+
+    type PoseidonTranscript<L, S> =
+        crate::system::halo2::transcript::halo2::PoseidonTranscript<G1Affine, L, S, 5, 4, 8, 60>;
+
+    run(|loader| {
+        let identity = G1Affine::identity();
+
+        let mut native = PoseidonTranscript::<NativeLoader, _>::new(Vec::<u8>::new());
+        assert!(native.common_ec_point(&identity).is_err());
+        native.set_strict_mode(StrictMode::Lenient);
+        native.common_ec_point(&identity).unwrap();
+        let challenge = native.squeeze_challenge();
+
+        // Lenient mode absorbs the identity as all-zero coordinates
+        let mut reference = PoseidonTranscript::<NativeLoader, _>::new(Vec::<u8>::new());
+        reference.common_scalar(&Fr::zero()).unwrap();
+        reference.common_scalar(&Fr::zero()).unwrap();
+        assert_eq!(reference.squeeze_challenge(), challenge);
+
+        let mut transcript =
+            PoseidonTranscript::<Rc<Halo2Loader>, _>::new(loader, Value::known([].as_slice()));
+        let identity = loader.ec_point_load_zero();
+        assert!(transcript.common_ec_point(&identity).is_err());
+        transcript.set_strict_mode(StrictMode::Lenient);
+        transcript.common_ec_point(&identity).unwrap();
+        loader
+            .assert_eq(
+                "",
+                &transcript.squeeze_challenge(),
+                &loader.load_const(&challenge),
+            )
+            .unwrap();
+    });
+}
+
+#[test]
+fn test_is_member_of() {
+    use crate::util::arithmetic::root_of_unity;
+
+    run(|loader| {
+        let omega = root_of_unity::<Fr>(2);
+        let roots = (0..4u64).map(|i| omega.pow_vartime([i])).collect_vec();
+
+        for (x, expected) in [(roots[2], Fr::one()), (Fr::from(42), Fr::zero())] {
+            assert_eq!(NativeLoader.is_member_of(&x, &roots), expected);
+            let assigned = loader.assign_scalar(Value::known(x));
+            let is_member = loader.is_member_of(&assigned, &roots);
+            loader
+                .assert_eq("", &is_member, &loader.load_const(&expected))
+                .unwrap();
+        }
+        assert_eq!(NativeLoader.is_member_of(&Fr::one(), &[]), Fr::zero());
+    });
+}
+
+#[test]
+fn test_assign_scalars() {
+    use crate::loader::halo2::PackingHint;
+
+    assert_eq!(
+        PackingHint::batch(3).collect_vec(),
+        [(0, 2), (1, 1), (2, 0)]
+            .into_iter()
+            .map(|(preceding, following)| PackingHint {
+                preceding,
+                following
+            })
+            .collect_vec()
+    );
+    assert_eq!(PackingHint::batch(0).count(), 0);
+
+    run(|loader| {
+        let values = (1..=4).map(Fr::from).collect_vec();
+
+        // Chips without packing assign a batch like separate scalars
+        let offset = loader.ctx().offset();
+        let scalars = loader.assign_scalars(values.iter().copied().map(Value::known));
+        let batched = loader.ctx().offset() - offset;
+        let offset = loader.ctx().offset();
+        for value in values.iter() {
+            loader.assign_scalar(Value::known(*value));
+        }
+        assert_eq!(batched, loader.ctx().offset() - offset);
+
+        for (scalar, value) in scalars.iter().zip(values) {
+            loader
+                .assert_eq("", scalar, &loader.load_const(&value))
+                .unwrap();
+        }
+    });
+}
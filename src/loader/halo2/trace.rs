@@ -0,0 +1,204 @@
+use crate::{
+    loader::{
+        halo2::{
+            loader::{EcPoint, Halo2Loader, Scalar},
+            shim::EccInstructions,
+        },
+        EcPointLoader, ScalarLoader,
+    },
+    util::{arithmetic::CurveAffine, Itertools},
+};
+use halo2_proofs::circuit::Value;
+use std::{collections::BTreeMap, rc::Rc};
+
+/// Operation issued to a [`Halo2Loader`], where operands and outputs are
+/// referred to by the index of the loaded scalar or point.
+#[derive(Clone, Debug)]
+pub enum Op<C: CurveAffine> {
+    LoadConst {
+        output: usize,
+        value: C::Scalar,
+    },
+    AssignConstScalar {
+        output: usize,
+        value: C::Scalar,
+    },
+    AssignScalar {
+        output: usize,
+        value: Value<C::Scalar>,
+    },
+    Add {
+        output: usize,
+        lhs: usize,
+        rhs: usize,
+    },
+    Sub {
+        output: usize,
+        lhs: usize,
+        rhs: usize,
+    },
+    Mul {
+        output: usize,
+        lhs: usize,
+        rhs: usize,
+    },
+    MulAdd {
+        output: usize,
+        a: usize,
+        b: usize,
+        c: usize,
+    },
+    Div {
+        output: usize,
+        lhs: usize,
+        rhs: usize,
+    },
+    Neg {
+        output: usize,
+        scalar: usize,
+    },
+    Invert {
+        output: usize,
+        scalar: usize,
+    },
+    SumWithCoeffAndConst {
+        output: usize,
+        values: Vec<(C::Scalar, usize)>,
+        constant: C::Scalar,
+    },
+    SumProductsWithCoeffAndConst {
+        output: usize,
+        values: Vec<(C::Scalar, usize, usize)>,
+        constant: C::Scalar,
+    },
+    AssertEq {
+        lhs: usize,
+        rhs: usize,
+    },
+    LoadConstEcPoint {
+        output: usize,
+        value: C,
+    },
+    AssignEcPoint {
+        output: usize,
+        value: Value<C>,
+    },
+    Msm {
+        output: usize,
+        pairs: Vec<(usize, usize)>,
+    },
+    EcPointAssertEq {
+        lhs: usize,
+        rhs: usize,
+    },
+}
+
+pub type OpTrace<C> = Vec<Op<C>>;
+
+/// Scalars and points produced by [`replay`], keyed by the indices they had
+/// in the recorded trace.
+#[derive(Debug)]
+pub struct Replay<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>> {
+    pub scalars: BTreeMap<usize, Scalar<'a, C, EccChip>>,
+    pub ec_points: BTreeMap<usize, EcPoint<'a, C, EccChip>>,
+}
+
+/// Re-issues the operations of `trace` on `loader`, which could be backed by
+/// a different chip than the one the trace was recorded with.
+///
+/// Panics if an operation refers to an index not produced earlier in `trace`.
+pub fn replay<'a, C: CurveAffine, EccChip: EccInstructions<'a, C>>(
+    trace: &[Op<C>],
+    loader: &Rc<Halo2Loader<'a, C, EccChip>>,
+) -> Result<Replay<'a, C, EccChip>, crate::Error> {
+    let mut scalars = BTreeMap::new();
+    let mut ec_points = BTreeMap::new();
+    for op in trace {
+        match op {
+            Op::LoadConst { output, value } => {
+                scalars.insert(*output, loader.load_const(value));
+            }
+            Op::AssignConstScalar { output, value } => {
+                scalars.insert(*output, loader.assign_const_scalar(*value));
+            }
+            Op::AssignScalar { output, value } => {
+                scalars.insert(*output, loader.assign_scalar(*value));
+            }
+            Op::Add { output, lhs, rhs } => {
+                let value = loader.try_add(&scalars[lhs], &scalars[rhs])?;
+                scalars.insert(*output, value);
+            }
+            Op::Sub { output, lhs, rhs } => {
+                let value = loader.try_sub(&scalars[lhs], &scalars[rhs])?;
+                scalars.insert(*output, value);
+            }
+            Op::Mul { output, lhs, rhs } => {
+                let value = loader.try_mul(&scalars[lhs], &scalars[rhs])?;
+                scalars.insert(*output, value);
+            }
+            Op::MulAdd { output, a, b, c } => {
+                let value = loader.try_mul_add(&scalars[a], &scalars[b], &scalars[c])?;
+                scalars.insert(*output, value);
+            }
+            Op::Div { output, lhs, rhs } => {
+                let value = loader.try_div(&scalars[lhs], &scalars[rhs])?;
+                scalars.insert(*output, value);
+            }
+            Op::Neg { output, scalar } => {
+                let value = loader.try_neg(&scalars[scalar])?;
+                scalars.insert(*output, value);
+            }
+            Op::Invert { output, scalar } => {
+                let value = loader.try_invert(&scalars[scalar])?;
+                scalars.insert(*output, value);
+            }
+            Op::SumWithCoeffAndConst {
+                output,
+                values,
+                constant,
+            } => {
+                let values = values
+                    .iter()
+                    .map(|(coeff, value)| (*coeff, &scalars[value]))
+                    .collect_vec();
+                let value = loader.sum_with_coeff_and_const(&values, *constant);
+                scalars.insert(*output, value);
+            }
+            Op::SumProductsWithCoeffAndConst {
+                output,
+                values,
+                constant,
+            } => {
+                let values = values
+                    .iter()
+                    .map(|(coeff, lhs, rhs)| (*coeff, &scalars[lhs], &scalars[rhs]))
+                    .collect_vec();
+                let value = loader.sum_products_with_coeff_and_const(&values, *constant);
+                scalars.insert(*output, value);
+            }
+            Op::AssertEq { lhs, rhs } => {
+                loader.assert_eq("replay", &scalars[lhs], &scalars[rhs])?;
+            }
+            Op::LoadConstEcPoint { output, value } => {
+                ec_points.insert(*output, loader.ec_point_load_const(value));
+            }
+            Op::AssignEcPoint { output, value } => {
+                ec_points.insert(*output, loader.assign_ec_point(*value));
+            }
+            Op::Msm { output, pairs } => {
+                let pairs = pairs
+                    .iter()
+                    .map(|(scalar, ec_point)| {
+                        (scalars[scalar].clone(), ec_points[ec_point].clone())
+                    })
+                    .collect_vec();
+                let value = loader.try_multi_scalar_multiplication(pairs)?;
+                ec_points.insert(*output, value);
+            }
+            Op::EcPointAssertEq { lhs, rhs } => {
+                loader.ec_point_assert_eq("replay", &ec_points[lhs], &ec_points[rhs])?;
+            }
+        }
+    }
+    Ok(Replay { scalars, ec_points })
+}
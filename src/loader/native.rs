@@ -1,9 +1,12 @@
 use crate::{
     loader::{EcPointLoader, LoadedEcPoint, LoadedScalar, Loader, ScalarLoader},
-    util::arithmetic::{Curve, CurveAffine, FieldOps, PrimeField},
+    util::arithmetic::{
+        fe_from_big, fe_to_big, modulus, Curve, CurveAffine, Field, FieldOps, PrimeField,
+    },
     Error,
 };
 use lazy_static::lazy_static;
+use num_bigint::BigUint;
 use std::fmt::Debug;
 
 lazy_static! {
@@ -25,8 +28,8 @@ impl<C: CurveAffine> LoadedEcPoint<C> for C {
             .into_iter()
             .map(|(scalar, base)| base * scalar)
             .reduce(|acc, value| acc + value)
-            .unwrap()
-            .to_affine()
+            .map(|value| value.to_affine())
+            .unwrap_or_else(C::identity)
     }
 }
 
@@ -61,6 +64,14 @@ impl<C: CurveAffine> EcPointLoader<C> for NativeLoader {
             .then_some(())
             .ok_or_else(|| Error::AssertionFailure(annotation.to_string()))
     }
+
+    fn points_equal(&self, lhs: &C, rhs: &C) -> C::Scalar {
+        if lhs == rhs {
+            C::Scalar::one()
+        } else {
+            C::Scalar::zero()
+        }
+    }
 }
 
 impl<F: PrimeField> ScalarLoader<F> for NativeLoader {
@@ -80,6 +91,47 @@ impl<F: PrimeField> ScalarLoader<F> for NativeLoader {
             .then_some(())
             .ok_or_else(|| Error::AssertionFailure(annotation.to_string()))
     }
+
+    fn is_equal(&self, lhs: &F, rhs: &F) -> F {
+        if lhs == rhs {
+            F::one()
+        } else {
+            F::zero()
+        }
+    }
+
+    fn to_bits(&self, x: &F, num_bits: usize) -> Result<Vec<F>, Error> {
+        let x = fe_to_big(*x);
+        if x.bits() > num_bits as u64 {
+            return Err(Error::AssertionFailure(format!(
+                "Value doesn't fit in {} bits",
+                num_bits
+            )));
+        }
+        Ok((0..num_bits)
+            .map(|idx| {
+                if x.bit(idx as u64) {
+                    F::one()
+                } else {
+                    F::zero()
+                }
+            })
+            .collect())
+    }
+
+    fn from_bytes_le(&self, bytes: &[F]) -> F {
+        let bytes = bytes
+            .iter()
+            .map(|byte| {
+                let byte = fe_to_big(*byte);
+                assert!(byte < BigUint::from(256usize));
+                byte.to_bytes_le().first().copied().unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+        let composed = BigUint::from_bytes_le(&bytes);
+        assert!(composed < modulus::<F>());
+        fe_from_big(composed)
+    }
 }
 
 impl<C: CurveAffine> Loader<C> for NativeLoader {}
@@ -8,7 +8,7 @@ use crate::{
     Error,
 };
 use rand::Rng;
-use std::fmt::Debug;
+use std::{fmt::Debug, marker::PhantomData};
 
 pub mod kzg;
 
@@ -37,6 +37,10 @@ impl<F: PrimeField> Query<F> {
     }
 }
 
+/// Reduction of the openings of a proof into an accumulator, which is what
+/// [`Plonk`](crate::verifier::Plonk) is generic over, e.g.
+/// [`Gwc19`](crate::pcs::kzg::Gwc19) for GWC and
+/// [`Bdfg21`](crate::pcs::kzg::Bdfg21) for SHPLONK.
 pub trait MultiOpenScheme<C, L>: PolynomialCommitmentScheme<C, L>
 where
     C: CurveAffine,
@@ -62,6 +66,67 @@ where
     ) -> Result<Self::Accumulator, Error>;
 }
 
+/// Opening reduction held as a value, so the scheme can be chosen where the
+/// verifier is set up, e.g. [`Gwc`](crate::pcs::kzg::Gwc) or
+/// [`Shplonk`](crate::pcs::kzg::Shplonk).
+pub trait OpeningScheme<C, L>: Clone + Debug
+where
+    C: CurveAffine,
+    L: Loader<C>,
+{
+    type Accumulator: Clone + Debug;
+    /// Evaluation point together with what the scheme reads from the
+    /// transcript, i.e. its challenges and quotient commitments.
+    type Challenges: Clone + Debug;
+
+    /// Reduces openings of `commitments` to `evals` into an accumulator.
+    fn reduce(
+        &self,
+        loader: &L,
+        commitments: &[Msm<C, L>],
+        evals: &[Query<C::Scalar, L::LoadedScalar>],
+        challenges: &Self::Challenges,
+    ) -> Result<Self::Accumulator, Error>;
+}
+
+/// [`OpeningScheme`] of a [`MultiOpenScheme`] `MOS` with its succinct
+/// verifying key `SVK`, which reduces by `MOS::succinct_verify`.
+#[derive(Clone, Debug)]
+pub struct MultiOpen<MOS, SVK> {
+    svk: SVK,
+    _marker: PhantomData<MOS>,
+}
+
+impl<MOS, SVK> MultiOpen<MOS, SVK> {
+    pub fn new(svk: SVK) -> Self {
+        Self {
+            svk,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<C, L, MOS, SVK> OpeningScheme<C, L> for MultiOpen<MOS, SVK>
+where
+    C: CurveAffine,
+    L: Loader<C>,
+    MOS: MultiOpenScheme<C, L, SuccinctVerifyingKey = SVK>,
+    SVK: Clone + Debug,
+{
+    type Accumulator = MOS::Accumulator;
+    type Challenges = (L::LoadedScalar, MOS::Proof);
+
+    fn reduce(
+        &self,
+        _: &L,
+        commitments: &[Msm<C, L>],
+        evals: &[Query<C::Scalar, L::LoadedScalar>],
+        (point, proof): &Self::Challenges,
+    ) -> Result<Self::Accumulator, Error> {
+        MOS::succinct_verify(&self.svk, commitments, point, evals, proof)
+    }
+}
+
 pub trait Decider<C, L>: PolynomialCommitmentScheme<C, L>
 where
     C: CurveAffine,
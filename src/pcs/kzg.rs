@@ -1,6 +1,6 @@
 use crate::{
     loader::Loader,
-    pcs::PolynomialCommitmentScheme,
+    pcs::{MultiOpen, PolynomialCommitmentScheme},
     util::arithmetic::{CurveAffine, MultiMillerLoop},
 };
 use std::{fmt::Debug, marker::PhantomData};
@@ -18,6 +18,16 @@ pub use multiopen::{Bdfg21, Bdfg21Proof, Gwc19, Gwc19Proof};
 #[derive(Clone, Debug)]
 pub struct Kzg<M, MOS>(PhantomData<(M, MOS)>);
 
+/// [`OpeningScheme`](crate::pcs::OpeningScheme) reducing KZG openings by
+/// [`Gwc19`].
+pub type Gwc<M> =
+    MultiOpen<Kzg<M, Gwc19>, KzgSuccinctVerifyingKey<<M as MultiMillerLoop>::G1Affine>>;
+
+/// [`OpeningScheme`](crate::pcs::OpeningScheme) reducing KZG openings by
+/// [`Bdfg21`], which is SHPLONK.
+pub type Shplonk<M> =
+    MultiOpen<Kzg<M, Bdfg21>, KzgSuccinctVerifyingKey<<M as MultiMillerLoop>::G1Affine>>;
+
 impl<M, L, MOS> PolynomialCommitmentScheme<M::G1Affine, L> for Kzg<M, MOS>
 where
     M: MultiMillerLoop,
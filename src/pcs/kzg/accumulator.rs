@@ -1,4 +1,8 @@
-use crate::{loader::Loader, util::arithmetic::CurveAffine};
+use crate::{
+    loader::Loader,
+    pcs::kzg::KzgSuccinctVerifyingKey,
+    util::{arithmetic::CurveAffine, msm::Msm},
+};
 use std::fmt::Debug;
 
 #[derive(Clone, Debug)]
@@ -19,6 +23,21 @@ where
     pub fn new(lhs: L::LoadedEcPoint, rhs: L::LoadedEcPoint) -> Self {
         Self { lhs, rhs }
     }
+
+    /// Returns the accumulator of a single opening of `commitment` to `v` at
+    /// `z` with quotient commitment `proof`, which checks
+    /// `e(commitment - [v] + [z] proof, [1]) == e(proof, [s])`.
+    pub fn from_opening(
+        svk: &KzgSuccinctVerifyingKey<C>,
+        commitment: &L::LoadedEcPoint,
+        z: &L::LoadedScalar,
+        v: &L::LoadedScalar,
+        proof: &L::LoadedEcPoint,
+    ) -> Self {
+        let lhs =
+            Msm::base(commitment.clone()) - Msm::constant(v.clone()) + Msm::base(proof.clone()) * z;
+        Self::new(lhs.evaluate(Some(svk.g)), proof.clone())
+    }
 }
 
 /// `AccumulatorEncoding` that encodes `Accumulator` into limbs.
@@ -125,7 +144,10 @@ mod evm {
 #[cfg(feature = "loader_halo2")]
 mod halo2 {
     use crate::{
-        loader::halo2::{Context, EccInstructions, Halo2Loader, Scalar, Valuetools},
+        loader::{
+            halo2::{Context, EcPoint, EccInstructions, Halo2Loader, Scalar, Valuetools},
+            LoadedEcPoint,
+        },
         pcs::{
             kzg::{KzgAccumulator, LimbsEncoding},
             AccumulatorEncoding, PolynomialCommitmentScheme,
@@ -159,6 +181,30 @@ mod halo2 {
         x.zip(y).map(|(x, y)| C::from_xy(x, y).unwrap())
     }
 
+    impl<'a, C, EccChip, const LIMBS: usize, const BITS: usize> EcPoint<'a, C, EccChip>
+    where
+        C: CurveAffine,
+        EccChip: EccInstructions<
+            'a,
+            C,
+            AssignedEcPoint = AssignedPoint<<C as CurveAffine>::Base, C::Scalar, LIMBS, BITS>,
+            AssignedScalar = AssignedValue<C::Scalar>,
+        >,
+    {
+        /// Returns limbs of x and then y, in the same order `LimbsEncoding`
+        /// reads them back, to be exposed as instances of the accumulator.
+        /// It fails for the identity, which has no affine coordinates.
+        pub fn into_accumulator_limbs(self) -> Result<Vec<AssignedValue<C::Scalar>>, Error> {
+            let assigned = self.assigned()?;
+            self.loader().mark_constrained(&[], &[self.index()]);
+            Ok(iter::empty()
+                .chain(assigned.x().limbs())
+                .chain(assigned.y().limbs())
+                .map(|limb| limb.as_ref().clone())
+                .collect())
+        }
+    }
+
     impl<'a, C, PCS, EccChip, const LIMBS: usize, const BITS: usize>
         AccumulatorEncoding<C, Rc<Halo2Loader<'a, C, EccChip>>, PCS> for LimbsEncoding<LIMBS, BITS>
     where
@@ -188,18 +234,23 @@ mod halo2 {
                 },
             );
 
+            let (lhs_assigned, rhs_assigned) = (lhs.assigned()?, rhs.assigned()?);
             for (src, dst) in assigned_limbs.iter().zip(
                 iter::empty()
-                    .chain(lhs.assigned().x().limbs())
-                    .chain(lhs.assigned().y().limbs())
-                    .chain(rhs.assigned().x().limbs())
-                    .chain(rhs.assigned().y().limbs()),
+                    .chain(lhs_assigned.x().limbs())
+                    .chain(lhs_assigned.y().limbs())
+                    .chain(rhs_assigned.x().limbs())
+                    .chain(rhs_assigned.y().limbs()),
             ) {
                 loader
                     .ctx_mut()
                     .constrain_equal(src.cell(), dst.as_ref().cell())
                     .unwrap();
             }
+            loader.mark_constrained(
+                &limbs.iter().map(|limb| limb.index()).collect_vec(),
+                &[lhs.index(), rhs.index()],
+            );
             let accumulator = KzgAccumulator::new(lhs, rhs);
 
             Ok(accumulator)
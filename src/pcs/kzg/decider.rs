@@ -160,3 +160,65 @@ mod evm {
         }
     }
 }
+
+#[cfg(feature = "loader_halo2")]
+mod halo2 {
+    use crate::{
+        loader::halo2::{EccInstructions, Halo2Loader},
+        pcs::{
+            kzg::{Kzg, KzgAccumulator, KzgDecidingKey},
+            Decider,
+        },
+        util::arithmetic::{CurveAffine, MultiMillerLoop},
+        Error,
+    };
+    use halo2_wrong_ecc::{maingate::AssignedValue, AssignedPoint};
+    use std::{fmt::Debug, iter, rc::Rc};
+
+    type Scalar<M> = <<M as MultiMillerLoop>::G1Affine as CurveAffine>::ScalarExt;
+    type Base<M> = <<M as MultiMillerLoop>::G1Affine as CurveAffine>::Base;
+
+    /// Pairing can't be checked in circuit, so deciding outputs limbs of
+    /// `lhs` and `rhs` to be exposed as instances and checked later.
+    impl<'a, M, MOS, EccChip, const LIMBS: usize, const BITS: usize>
+        Decider<M::G1Affine, Rc<Halo2Loader<'a, M::G1Affine, EccChip>>> for Kzg<M, MOS>
+    where
+        M: MultiMillerLoop,
+        MOS: Clone + Debug,
+        EccChip: EccInstructions<
+            'a,
+            M::G1Affine,
+            AssignedEcPoint = AssignedPoint<Base<M>, Scalar<M>, LIMBS, BITS>,
+            AssignedScalar = AssignedValue<Scalar<M>>,
+        >,
+    {
+        type DecidingKey = KzgDecidingKey<M>;
+        type Output = Result<Vec<AssignedValue<Scalar<M>>>, Error>;
+
+        fn decide(
+            _: &Self::DecidingKey,
+            KzgAccumulator { lhs, rhs }: KzgAccumulator<
+                M::G1Affine,
+                Rc<Halo2Loader<'a, M::G1Affine, EccChip>>,
+            >,
+        ) -> Self::Output {
+            Ok(iter::empty()
+                .chain(lhs.into_accumulator_limbs()?)
+                .chain(rhs.into_accumulator_limbs()?)
+                .collect())
+        }
+
+        fn decide_all(
+            dk: &Self::DecidingKey,
+            accumulators: Vec<
+                KzgAccumulator<M::G1Affine, Rc<Halo2Loader<'a, M::G1Affine, EccChip>>>,
+            >,
+        ) -> Self::Output {
+            let mut limbs = Vec::new();
+            for accumulator in accumulators {
+                limbs.extend(Self::decide(dk, accumulator)?);
+            }
+            Ok(limbs)
+        }
+    }
+}
@@ -18,6 +18,10 @@ use std::{
     marker::PhantomData,
 };
 
+/// Multi-open scheme of [BDFG21] ("SHPLONK" in halo2), reducing openings at
+/// all points into a single KZG proof.
+///
+/// [BDFG21]: https://eprint.iacr.org/2020/081
 #[derive(Clone, Debug)]
 pub struct Bdfg21;
 
@@ -376,6 +380,12 @@ where
     type Input = Vec<Query<M::Scalar>>;
 
     fn estimate_cost(_: &Vec<Query<M::Scalar>>) -> Cost {
-        Cost::new(0, 2, 0, 2)
+        // Accumulator's lhs takes `w` and `w'` on top of the verifier's terms,
+        // and its rhs is `w'` alone
+        Cost {
+            msm_arities: vec![2, 1],
+            num_pairings: 2,
+            ..Cost::new(0, 2, 0, 2)
+        }
     }
 }
@@ -14,6 +14,10 @@ use crate::{
     Error,
 };
 
+/// Multi-open scheme of [GWC19] ("PLONK" in halo2), opening each distinct
+/// point with its own KZG proof.
+///
+/// [GWC19]: https://eprint.iacr.org/2019/953
 #[derive(Clone, Debug)]
 pub struct Gwc19;
 
@@ -162,6 +166,12 @@ where
 
     fn estimate_cost(queries: &Vec<Query<M::Scalar>>) -> Cost {
         let num_w = query_sets(queries).len();
-        Cost::new(0, num_w, 0, num_w)
+        // Accumulator's lhs takes a term per `w` on top of the verifier's,
+        // and its rhs is made of the `w`s alone
+        Cost {
+            msm_arities: vec![num_w, num_w],
+            num_pairings: 2,
+            ..Cost::new(0, num_w, 0, num_w)
+        }
     }
 }
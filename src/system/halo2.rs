@@ -148,6 +148,7 @@ pub fn compile<'a, C: CurveAffine, P: Params<'a, C>>(
 
     Protocol {
         domain,
+        num_blinding_factors: polynomials.num_blinding_factors(),
         preprocessed,
         num_instance: polynomials.num_instance(),
         num_witness: polynomials.num_witness(),
@@ -500,8 +501,16 @@ impl<'a, F: FieldExt> Polynomials<'a, F> {
         })
     }
 
+    fn num_blinding_factors(&self) -> usize {
+        if self.zk {
+            self.cs.blinding_factors()
+        } else {
+            0
+        }
+    }
+
     fn rotation_last(&self) -> Rotation {
-        Rotation(-((self.cs.blinding_factors() + 1) as i32))
+        Rotation(-((self.num_blinding_factors() + 1) as i32))
     }
 
     fn l_last(&self) -> Expression<F> {
@@ -118,3 +118,61 @@ macro_rules! halo2_kzg_native_verify {
 pub(crate) use {
     halo2_kzg_config, halo2_kzg_create_snark, halo2_kzg_native_verify, halo2_kzg_prepare,
 };
+
+#[cfg(feature = "cost_estimate")]
+#[test]
+fn test_estimate() {
+    use crate::{
+        cost::{estimate, CostEstimate},
+        pcs::kzg::{Gwc19, Kzg},
+        util::{
+            arithmetic::{root_of_unity, Domain, PrimeCurveAffine, Rotation},
+            protocol::{Expression, Query, QuotientPolynomial},
+        },
+        verifier::Plonk,
+        Protocol,
+    };
+    use halo2_curves::bn256::{Bn256, G1Affine};
+
+    let queries = [(0, 0), (3, 0), (3, 1)]
+        .map(|(poly, rotation)| Query::new(poly, Rotation(rotation)))
+        .to_vec();
+    let poly = |query: &Query| Box::new(Expression::Polynomial(*query));
+    let protocol = Protocol::<G1Affine> {
+        domain: Domain::new(4, root_of_unity(4)),
+        num_blinding_factors: 0,
+        preprocessed: vec![G1Affine::generator(); 2],
+        num_instance: vec![1],
+        num_witness: vec![2],
+        num_challenge: vec![1],
+        evaluations: queries.clone(),
+        queries: queries.clone(),
+        // Degree 3 numerator with chunk degree 1 takes 2 chunks
+        quotient: QuotientPolynomial {
+            chunk_degree: 1,
+            numerator: Expression::Product(
+                Box::new(Expression::Product(poly(&queries[0]), poly(&queries[1]))),
+                poly(&queries[2]),
+            ),
+        },
+        transcript_initial_state: None,
+        instance_committing_key: None,
+        linearization: None,
+        accumulator_indices: Vec::new(),
+    };
+
+    // 2 witnesses, 2 quotient chunks and 2 opening proofs for 2 distinct
+    // rotations, and 3 evaluations, all 32 bytes each.
+    // Msm takes 2 preprocessed, 4 commitments, 1 for evaluations and 2
+    // opening proofs, which make the accumulator's lhs whose rhs is the 2
+    // opening proofs, checked with 2 pairings.
+    assert_eq!(
+        estimate::<_, (G1Affine, Kzg<Bn256, Gwc19>), Plonk<Kzg<Bn256, Gwc19>>>(&protocol),
+        CostEstimate {
+            proof_bytes: 9 * 32,
+            num_msm: 9,
+            msm_arities: vec![9, 2],
+            num_pairings: 2,
+        }
+    );
+}
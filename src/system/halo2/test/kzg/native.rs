@@ -66,3 +66,377 @@ test!(
     halo2_kzg_config!(true, 2, (0..4 * LIMBS).map(|idx| (0, idx)).collect()),
     main_gate_with_range_with_mock_kzg_accumulator::<Bn256>()
 );
+
+#[test]
+fn test_verifier_trace() {
+    use crate::{
+        loader::native::NativeLoader,
+        verifier::{Milestone, PlonkVerifier},
+    };
+    use halo2_proofs::poly::commitment::ParamsProver;
+
+    type Pcs = Kzg<Bn256, Gwc19>;
+    type Verifier = Plonk<Pcs, LimbsEncoding<LIMBS, BITS>>;
+
+    let (params, pk, protocol, circuits) = halo2_kzg_prepare!(
+        9,
+        halo2_kzg_config!(true, 1),
+        StandardPlonk::rand(ChaCha20Rng::from_seed(Default::default()))
+    );
+    let snark = halo2_kzg_create_snark!(
+        ProverGWC<_>,
+        VerifierGWC<_>,
+        Blake2bWrite<_, _, _>,
+        Blake2bRead<_, _, _>,
+        Challenge255<_>,
+        &params,
+        &pk,
+        &protocol,
+        &circuits
+    );
+
+    let svk = params.get_g()[0].into();
+    let mut transcript = Blake2bRead::<_, G1Affine, _>::init(snark.proof.as_slice());
+    let proof = <Verifier as PlonkVerifier<G1Affine, NativeLoader, Pcs>>::read_proof(
+        &svk,
+        &snark.protocol,
+        &snark.instances,
+        &mut transcript,
+    )
+    .unwrap();
+    let (accumulators, trace) =
+        Verifier::succinct_verify_with_trace(&svk, &snark.protocol, &snark.instances, &proof)
+            .unwrap();
+
+    let names = trace.names();
+    for name in [
+        "witness commitment 0",
+        "challenge 0",
+        "quotient commitment 0",
+        "z",
+        "quotient evaluation",
+        "opening accumulator",
+    ] {
+        assert!(names.contains(&name), "missing milestone {name}");
+    }
+    assert!(matches!(trace.get("z"), Some(Milestone::Scalar(z)) if *z == proof.z));
+    match trace.get("opening accumulator") {
+        Some(Milestone::Accumulator(accumulator)) => {
+            assert_eq!(accumulator.lhs, accumulators[0].lhs);
+            assert_eq!(accumulator.rhs, accumulators[0].rhs);
+        }
+        _ => panic!("opening accumulator should be an accumulator"),
+    }
+}
+
+#[test]
+fn test_blinding_factors() {
+    use crate::{
+        loader::native::NativeLoader,
+        util::{
+            arithmetic::{Field, Rotation},
+            protocol::{CommonPolynomial, CommonPolynomialEvaluation},
+        },
+    };
+    use halo2_curves::bn256::Fr;
+
+    let (_, pk, protocol, _) = halo2_kzg_prepare!(
+        9,
+        halo2_kzg_config!(true, 1),
+        StandardPlonk::rand(ChaCha20Rng::from_seed(Default::default()))
+    );
+    assert_eq!(
+        protocol.num_blinding_factors,
+        pk.get_vk().cs().blinding_factors()
+    );
+
+    // Last usable row is right before the blinded ones
+    let rotation_last = -(protocol.num_blinding_factors as i32 + 1);
+    assert!(protocol
+        .quotient
+        .numerator
+        .used_langrange()
+        .contains(&rotation_last));
+
+    let z = Fr::from(7);
+    let mut evaluation = CommonPolynomialEvaluation::<G1Affine, NativeLoader>::new(
+        &protocol.domain,
+        [rotation_last],
+        &z,
+    );
+    for denom in evaluation.denoms() {
+        *denom = Field::invert(denom).unwrap();
+    }
+    evaluation.evaluate();
+
+    let domain = &protocol.domain;
+    let omega_last = domain.rotate_scalar(Fr::one(), Rotation(rotation_last));
+    let expected = (0..domain.n as i32)
+        .map(|idx| domain.rotate_scalar(Fr::one(), Rotation(idx)))
+        .filter(|omega| *omega != omega_last)
+        .fold(Fr::one(), |acc, omega| {
+            acc * (z - omega) * (omega_last - omega).invert().unwrap()
+        });
+    assert_eq!(
+        *evaluation.get(CommonPolynomial::Lagrange(rotation_last)),
+        expected
+    );
+
+    // Vanishing polynomial spans blinded rows as well
+    let vanishing = (0..domain.n as i32)
+        .map(|idx| domain.rotate_scalar(Fr::one(), Rotation(idx)))
+        .fold(Fr::one(), |acc, omega| acc * (z - omega));
+    assert_eq!(*evaluation.zn_minus_one(), vanishing);
+}
+
+#[test]
+fn test_rotated_queries() {
+    use crate::util::arithmetic::Rotation;
+    use halo2_curves::bn256::Fr;
+
+    let (_, _, protocol, _) = halo2_kzg_prepare!(
+        9,
+        halo2_kzg_config!(true, 1),
+        StandardPlonk::rand(ChaCha20Rng::from_seed(Default::default()))
+    );
+    let rotations = protocol
+        .queries
+        .iter()
+        .map(|query| query.rotation.0)
+        .collect::<std::collections::BTreeSet<_>>();
+    // Permutation argument opens z at the next row
+    assert!(rotations.contains(&1));
+
+    // Rotated points opened by the verifier are `z * omega^i`
+    let z = Fr::from(7);
+    let domain = &protocol.domain;
+    for rotation in [-1, 1].into_iter().chain(rotations) {
+        let omega = if rotation < 0 {
+            domain.gen_inv
+        } else {
+            domain.gen
+        };
+        let expected = (0..rotation.abs()).fold(z, |acc, _| acc * omega);
+        assert_eq!(domain.rotate_scalar(z, Rotation(rotation)), expected);
+    }
+    assert_eq!(
+        domain.rotate_scalar(domain.rotate_scalar(z, Rotation(1)), Rotation(-1)),
+        z
+    );
+}
+
+#[test]
+fn test_shared_transcript() {
+    use crate::{loader::native::NativeLoader, util::Itertools, verifier::PlonkVerifier};
+    use halo2_proofs::{
+        plonk::create_proof,
+        poly::{commitment::ParamsProver, kzg::commitment::KZGCommitmentScheme},
+        transcript::TranscriptWriterBuffer,
+    };
+
+    type Pcs = Kzg<Bn256, Gwc19>;
+    type Verifier = Plonk<Pcs, LimbsEncoding<LIMBS, BITS>>;
+
+    let (params, pk, protocol, circuits) = halo2_kzg_prepare!(
+        9,
+        halo2_kzg_config!(true, 1),
+        StandardPlonk::rand(ChaCha20Rng::from_seed(Default::default()))
+    );
+    let instances = circuits[0].instances();
+    let instance_slices = instances.iter().map(Vec::as_slice).collect_vec();
+
+    // Both proofs are written into one transcript, so the second one's
+    // challenges depend on the first one
+    let proof = {
+        let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(Vec::new());
+        for _ in 0..2 {
+            create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<_>, _, _, _, _>(
+                &params,
+                &pk,
+                &circuits,
+                &[instance_slices.as_slice()],
+                ChaCha20Rng::from_seed(Default::default()),
+                &mut transcript,
+            )
+            .unwrap();
+        }
+        transcript.finalize()
+    };
+
+    let svk = params.get_g()[0].into();
+    let dk = (params.g2(), params.s_g2()).into();
+    let mut transcript = Blake2bRead::<_, G1Affine, _>::init(proof.as_slice());
+    let proofs = (0..2)
+        .map(|_| {
+            <Verifier as PlonkVerifier<G1Affine, NativeLoader, Pcs>>::read_proof(
+                &svk,
+                &protocol,
+                &instances,
+                &mut transcript,
+            )
+            .unwrap()
+        })
+        .collect_vec();
+    assert_ne!(proofs[0].z, proofs[1].z);
+    for proof in proofs {
+        assert!(
+            <Verifier as PlonkVerifier<G1Affine, NativeLoader, Pcs>>::verify(
+                &svk, &dk, &protocol, &instances, &proof
+            )
+            .unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_swap_multi_open_scheme() {
+    use crate::{
+        loader::{halo2::test::Snark, native::NativeLoader},
+        pcs::{
+            kzg::{KzgDecidingKey, KzgSuccinctVerifyingKey},
+            AccumulatorEncoding, Decider, MultiOpenScheme,
+        },
+        verifier::PlonkVerifier,
+    };
+    use halo2_proofs::poly::{commitment::ParamsProver, kzg::commitment::ParamsKZG};
+
+    fn verify<MOS>(params: &ParamsKZG<Bn256>, snark: &Snark<G1Affine>) -> bool
+    where
+        MOS: MultiOpenScheme<
+                G1Affine,
+                NativeLoader,
+                SuccinctVerifyingKey = KzgSuccinctVerifyingKey<G1Affine>,
+            > + Decider<G1Affine, NativeLoader, DecidingKey = KzgDecidingKey<Bn256>, Output = bool>,
+        LimbsEncoding<LIMBS, BITS>: AccumulatorEncoding<G1Affine, NativeLoader, MOS>,
+    {
+        type Verifier<MOS> = Plonk<MOS, LimbsEncoding<LIMBS, BITS>>;
+
+        let svk = params.get_g()[0].into();
+        let dk = (params.g2(), params.s_g2()).into();
+        let mut transcript = Blake2bRead::<_, G1Affine, _>::init(snark.proof.as_slice());
+        <Verifier<MOS> as PlonkVerifier<G1Affine, NativeLoader, MOS>>::read_proof(
+            &svk,
+            &snark.protocol,
+            &snark.instances,
+            &mut transcript,
+        )
+        .and_then(|proof| {
+            <Verifier<MOS> as PlonkVerifier<G1Affine, NativeLoader, MOS>>::verify(
+                &svk,
+                &dk,
+                &snark.protocol,
+                &snark.instances,
+                &proof,
+            )
+        })
+        .unwrap_or(false)
+    }
+
+    let (params, pk, protocol, circuits) = halo2_kzg_prepare!(
+        9,
+        halo2_kzg_config!(true, 1),
+        StandardPlonk::rand(ChaCha20Rng::from_seed(Default::default()))
+    );
+    // Both proofs are accepted by halo2's own verifiers when created
+    let shplonk = halo2_kzg_create_snark!(
+        ProverSHPLONK<_>,
+        VerifierSHPLONK<_>,
+        Blake2bWrite<_, _, _>,
+        Blake2bRead<_, _, _>,
+        Challenge255<_>,
+        &params,
+        &pk,
+        &protocol,
+        &circuits
+    );
+    let gwc = halo2_kzg_create_snark!(
+        ProverGWC<_>,
+        VerifierGWC<_>,
+        Blake2bWrite<_, _, _>,
+        Blake2bRead<_, _, _>,
+        Challenge255<_>,
+        &params,
+        &pk,
+        &protocol,
+        &circuits
+    );
+
+    // Only the scheme matching the prover's accepts the proof
+    assert!(verify::<Kzg<Bn256, Bdfg21>>(&params, &shplonk));
+    assert!(!verify::<Kzg<Bn256, Gwc19>>(&params, &shplonk));
+    assert!(verify::<Kzg<Bn256, Gwc19>>(&params, &gwc));
+    assert!(!verify::<Kzg<Bn256, Bdfg21>>(&params, &gwc));
+}
+
+#[test]
+fn test_opening_scheme() {
+    use crate::{
+        loader::native::NativeLoader,
+        pcs::{
+            kzg::{Gwc, KzgAccumulator, KzgSuccinctVerifyingKey, Shplonk},
+            MultiOpen, MultiOpenScheme, OpeningScheme, Query,
+        },
+        util::{
+            arithmetic::{Field, PrimeCurveAffine},
+            msm::Msm,
+        },
+    };
+    use halo2_curves::bn256::Fr;
+    use halo2_proofs::transcript::{TranscriptWrite, TranscriptWriterBuffer};
+
+    fn reduce<MOS>(scheme: &MultiOpen<MOS, KzgSuccinctVerifyingKey<G1Affine>>)
+    where
+        MOS: MultiOpenScheme<
+            G1Affine,
+            NativeLoader,
+            SuccinctVerifyingKey = KzgSuccinctVerifyingKey<G1Affine>,
+            Accumulator = KzgAccumulator<G1Affine, NativeLoader>,
+        >,
+    {
+        let svk = KzgSuccinctVerifyingKey::new(G1Affine::generator());
+        let commitments = [3, 5]
+            .map(|scalar| Msm::base((G1Affine::generator() * Fr::from(scalar)).into()))
+            .to_vec();
+        let queries = [(0, 1), (1, 1), (1, 7)]
+            .map(|(poly, eval)| Query {
+                poly,
+                shift: Fr::one(),
+                eval: Fr::from(eval),
+            })
+            .to_vec();
+        let plain_queries = queries
+            .iter()
+            .map(|query| Query {
+                poly: query.poly,
+                shift: query.shift,
+                eval: (),
+            })
+            .collect::<Vec<_>>();
+
+        // Quotient commitments are arbitrary, only the reductions are compared
+        let proof = {
+            let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(Vec::new());
+            for scalar in [11, 13] {
+                transcript
+                    .write_point((G1Affine::generator() * Fr::from(scalar)).into())
+                    .unwrap();
+            }
+            transcript.finalize()
+        };
+        let read = || {
+            let mut transcript = Blake2bRead::<_, G1Affine, _>::init(proof.as_slice());
+            MOS::read_proof(&svk, &plain_queries, &mut transcript).unwrap()
+        };
+
+        let z = Fr::from(17);
+        let expected = MOS::succinct_verify(&svk, &commitments, &z, &queries, &read()).unwrap();
+        let reduced = scheme
+            .reduce(&NativeLoader, &commitments, &queries, &(z, read()))
+            .unwrap();
+        assert_eq!((reduced.lhs, reduced.rhs), (expected.lhs, expected.rhs));
+    }
+
+    let svk = KzgSuccinctVerifyingKey::new(G1Affine::generator());
+    reduce(&Gwc::<Bn256>::new(svk));
+    reduce(&Shplonk::<Bn256>::new(svk));
+}
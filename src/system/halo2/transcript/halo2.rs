@@ -1,14 +1,15 @@
 use crate::{
     loader::{
-        halo2::{self, EcPoint, EccInstructions, Halo2Loader, IntegerInstructions, Scalar},
+        halo2::{EcPoint, EccInstructions, Halo2Loader, IntegerInstructions, Scalar},
         native::{self, NativeLoader},
         Loader, ScalarLoader,
     },
     util::{
-        arithmetic::{fe_from_big, fe_to_big, CurveAffine, FieldExt, PrimeField},
+        arithmetic::{CurveAffine, PrimeField},
         hash::Poseidon,
-        transcript::{Transcript, TranscriptRead, TranscriptWrite},
-        Itertools,
+        transcript::{
+            ec_point_to_native_with_mode, StrictMode, Transcript, TranscriptRead, TranscriptWrite,
+        },
     },
     Error,
 };
@@ -22,14 +23,6 @@ use std::{
     rc::Rc,
 };
 
-pub trait EncodeNative<'a, C: CurveAffine, N: FieldExt>: EccInstructions<'a, C> {
-    fn encode_native(
-        &self,
-        ctx: &mut Self::Context,
-        ec_point: &Self::AssignedEcPoint,
-    ) -> Result<Vec<AssignedCell<N, N>>, Error>;
-}
-
 pub struct PoseidonTranscript<
     C: CurveAffine,
     L: Loader<C>,
@@ -42,14 +35,32 @@ pub struct PoseidonTranscript<
     loader: L,
     stream: S,
     buf: Poseidon<C::Scalar, <L as ScalarLoader<C::Scalar>>::LoadedScalar, T, RATE>,
+    strict_mode: StrictMode,
     _marker: PhantomData<C>,
 }
 
+impl<
+        C: CurveAffine,
+        L: Loader<C>,
+        S,
+        const T: usize,
+        const RATE: usize,
+        const R_F: usize,
+        const R_P: usize,
+    > PoseidonTranscript<C, L, S, T, RATE, R_F, R_P>
+{
+    /// Sets whether the identity point can be absorbed, which should match
+    /// the convention of the transcript the proof was created with.
+    pub fn set_strict_mode(&mut self, strict_mode: StrictMode) {
+        self.strict_mode = strict_mode;
+    }
+}
+
 impl<
         'a,
         C: CurveAffine,
         R: Read,
-        EccChip: EncodeNative<'a, C, C::Scalar, AssignedScalar = AssignedCell<C::Scalar, C::Scalar>>,
+        EccChip: EccInstructions<'a, C, AssignedScalar = AssignedCell<C::Scalar, C::Scalar>>,
         const T: usize,
         const RATE: usize,
         const R_F: usize,
@@ -61,6 +72,7 @@ impl<
             loader: loader.clone(),
             stream,
             buf: Poseidon::new(loader.clone(), R_F, R_P),
+            strict_mode: StrictMode::default(),
             _marker: PhantomData,
         }
     }
@@ -70,7 +82,7 @@ impl<
         'a,
         C: CurveAffine,
         R: Read,
-        EccChip: EncodeNative<'a, C, C::Scalar, AssignedScalar = AssignedCell<C::Scalar, C::Scalar>>,
+        EccChip: EccInstructions<'a, C, AssignedScalar = AssignedCell<C::Scalar, C::Scalar>>,
         const T: usize,
         const RATE: usize,
         const R_F: usize,
@@ -92,19 +104,20 @@ impl<
     }
 
     fn common_ec_point(&mut self, ec_point: &EcPoint<'a, C, EccChip>) -> Result<(), Error> {
-        let encoded = self
-            .loader
-            .ecc_chip()
-            .encode_native(&mut self.loader.ctx_mut(), &ec_point.assigned())
-            .map(|encoded| {
-                encoded
-                    .into_iter()
-                    .map(|encoded| self.loader.scalar(halo2::loader::Value::Assigned(encoded)))
-                    .collect_vec()
-            })
-            .map_err(|_| Error::Transcript(io::ErrorKind::Other, "".to_string()))?;
-        self.buf.update(&encoded);
-        Ok(())
+        if ec_point.is_identity_const() {
+            return match self.strict_mode {
+                StrictMode::Strict => Err(Error::Transcript(
+                    io::ErrorKind::Other,
+                    "Identity can't be absorbed in strict mode".to_string(),
+                )),
+                StrictMode::Lenient => {
+                    let zero = self.loader.load_zero();
+                    self.buf.update(&[zero.clone(), zero]);
+                    Ok(())
+                }
+            };
+        }
+        ec_point.absorb_into(self)
     }
 }
 
@@ -112,7 +125,7 @@ impl<
         'a,
         C: CurveAffine,
         R: Read,
-        EccChip: EncodeNative<'a, C, C::Scalar, AssignedScalar = AssignedCell<C::Scalar, C::Scalar>>,
+        EccChip: EccInstructions<'a, C, AssignedScalar = AssignedCell<C::Scalar, C::Scalar>>,
         const T: usize,
         const RATE: usize,
         const R_F: usize,
@@ -161,6 +174,7 @@ impl<C: CurveAffine, S, const T: usize, const RATE: usize, const R_F: usize, con
             loader: NativeLoader,
             stream,
             buf: Poseidon::new(NativeLoader, R_F, R_P),
+            strict_mode: StrictMode::default(),
             _marker: PhantomData,
         }
     }
@@ -183,18 +197,13 @@ impl<C: CurveAffine, S, const T: usize, const RATE: usize, const R_F: usize, con
     }
 
     fn common_ec_point(&mut self, ec_point: &C) -> Result<(), Error> {
-        let encoded: Vec<_> = Option::from(ec_point.coordinates().map(|coordinates| {
-            [coordinates.x(), coordinates.y()]
-                .into_iter()
-                .map(|fe| fe_from_big(fe_to_big(*fe)))
-                .collect_vec()
-        }))
-        .ok_or_else(|| {
-            Error::Transcript(
-                io::ErrorKind::Other,
-                "Invalid elliptic curve point encoding in proof".to_string(),
-            )
-        })?;
+        let encoded =
+            ec_point_to_native_with_mode(ec_point, self.strict_mode).ok_or_else(|| {
+                Error::Transcript(
+                    io::ErrorKind::Other,
+                    "Invalid elliptic curve point encoding in proof".to_string(),
+                )
+            })?;
         self.buf.update(&encoded);
         Ok(())
     }
@@ -415,25 +424,3 @@ impl<
         self.finalize()
     }
 }
-
-mod halo2_wrong {
-    use crate::system::halo2::transcript::halo2::EncodeNative;
-    use halo2_curves::CurveAffine;
-    use halo2_proofs::circuit::AssignedCell;
-    use halo2_wrong_ecc::BaseFieldEccChip;
-
-    impl<'a, C: CurveAffine, const LIMBS: usize, const BITS: usize> EncodeNative<'a, C, C::Scalar>
-        for BaseFieldEccChip<C, LIMBS, BITS>
-    {
-        fn encode_native(
-            &self,
-            _: &mut Self::Context,
-            ec_point: &Self::AssignedEcPoint,
-        ) -> Result<Vec<AssignedCell<C::Scalar, C::Scalar>>, crate::Error> {
-            Ok(vec![
-                ec_point.x().native().clone(),
-                ec_point.y().native().clone(),
-            ])
-        }
-    }
-}
@@ -1,5 +1,5 @@
 use crate::{
-    loader::{LoadedEcPoint, Loader},
+    loader::{LoadedEcPoint, LoadedScalar, Loader},
     util::arithmetic::CurveAffine,
 };
 use std::{
@@ -8,8 +8,13 @@ use std::{
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
+/// Terms of a multi-scalar multiplication accumulated across verifier steps,
+/// merging terms of the same base, and only computed on `evaluate`. The
+/// loader of the first term is kept, so an msm whose terms are all gone still
+/// evaluates to the identity.
 #[derive(Clone, Debug)]
 pub struct Msm<C: CurveAffine, L: Loader<C>> {
+    loader: Option<L>,
     constant: Option<L::LoadedScalar>,
     scalars: Vec<L::LoadedScalar>,
     bases: Vec<L::LoadedEcPoint>,
@@ -22,6 +27,7 @@ where
 {
     fn default() -> Self {
         Self {
+            loader: None,
             constant: None,
             scalars: Vec::new(),
             bases: Vec::new(),
@@ -36,15 +42,17 @@ where
 {
     pub fn constant(constant: L::LoadedScalar) -> Self {
         Msm {
+            loader: Some(constant.loader().clone()),
             constant: Some(constant),
             ..Default::default()
         }
     }
 
     pub fn base(base: L::LoadedEcPoint) -> Self {
-        let one = base.loader().load_one();
+        let loader = base.loader().clone();
         Msm {
-            scalars: vec![one],
+            scalars: vec![loader.load_one()],
+            loader: Some(loader),
             bases: vec![base],
             ..Default::default()
         }
@@ -63,19 +71,22 @@ where
         self.bases.is_empty().then(|| self.constant.unwrap())
     }
 
+    /// Computes the msm with the constant term scaling `gen`, which is the
+    /// identity when there are no terms. Only an msm that never had any term
+    /// has no loader to load the identity with.
     pub fn evaluate(self, gen: Option<C>) -> L::LoadedEcPoint {
-        let gen = gen.map(|gen| {
-            self.bases
-                .first()
-                .unwrap()
-                .loader()
-                .ec_point_load_const(&gen)
-        });
-        L::LoadedEcPoint::multi_scalar_multiplication(
-            iter::empty()
-                .chain(self.constant.map(|constant| (constant, gen.unwrap())))
-                .chain(self.scalars.into_iter().zip(self.bases.into_iter())),
-        )
+        let loader = self
+            .loader
+            .expect("Msm never had a term to take the loader from");
+        let gen = gen.map(|gen| loader.ec_point_load_const(&gen));
+        let pairs = iter::empty()
+            .chain(self.constant.map(|constant| (constant, gen.unwrap())))
+            .chain(self.scalars.into_iter().zip(self.bases.into_iter()))
+            .collect::<Vec<_>>();
+        if pairs.is_empty() {
+            return loader.ec_point_load_zero();
+        }
+        L::LoadedEcPoint::multi_scalar_multiplication(pairs)
     }
 
     pub fn scale(&mut self, factor: &L::LoadedScalar) {
@@ -88,6 +99,9 @@ where
     }
 
     pub fn push(&mut self, scalar: L::LoadedScalar, base: L::LoadedEcPoint) {
+        if self.loader.is_none() {
+            self.loader = Some(base.loader().clone());
+        }
         if let Some(pos) = self.bases.iter().position(|exist| exist.eq(&base)) {
             self.scalars[pos] += scalar;
         } else {
@@ -97,6 +111,9 @@ where
     }
 
     pub fn extend(&mut self, mut other: Self) {
+        if self.loader.is_none() {
+            self.loader = other.loader.take();
+        }
         match (self.constant.as_mut(), other.constant.as_ref()) {
             (Some(lhs), Some(rhs)) => *lhs += rhs,
             (None, Some(_)) => self.constant = other.constant.take(),
@@ -108,6 +125,48 @@ where
     }
 }
 
+/// Handle collecting `(scalar, point)` terms pushed across verifier steps,
+/// so they all go into the single `msm` run by `finalize`.
+#[derive(Clone, Debug)]
+pub struct Accumulator<C: CurveAffine, L: Loader<C>> {
+    msm: Msm<C, L>,
+}
+
+impl<C, L> Accumulator<C, L>
+where
+    C: CurveAffine,
+    L: Loader<C>,
+{
+    pub fn new(loader: &L) -> Self {
+        Self {
+            msm: Msm {
+                loader: Some(loader.clone()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Returns the number of distinct points pushed so far.
+    pub fn len(&self) -> usize {
+        self.msm.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds `scalar * point`, merged into an earlier term of the same point.
+    pub fn push(&mut self, scalar: L::LoadedScalar, point: L::LoadedEcPoint) {
+        self.msm.push(scalar, point);
+    }
+
+    /// Computes the sum of all pushed terms, which is the identity when none
+    /// were pushed.
+    pub fn finalize(self) -> L::LoadedEcPoint {
+        self.msm.evaluate(None)
+    }
+}
+
 impl<C, L> Add<Msm<C, L>> for Msm<C, L>
 where
     C: CurveAffine,
@@ -1,15 +1,16 @@
 use crate::{
-    loader::{LoadedScalar, Loader},
+    loader::{LoadedScalar, Loader, ScalarLoader},
     util::{
-        arithmetic::{CurveAffine, Domain, Field, Fraction, Rotation},
+        arithmetic::{CurveAffine, Domain, Field, Fraction, PrimeField, Rotation},
         Itertools,
     },
+    Error,
 };
 use num_integer::Integer;
 use num_traits::One;
 use std::{
     cmp::max,
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::Debug,
     iter::{self, Sum},
     ops::{Add, Mul, Neg, Sub},
@@ -77,6 +78,8 @@ where
         &self.zn
     }
 
+    /// Returns the vanishing polynomial `z^n - 1` of the whole domain,
+    /// blinded rows included, which is what the prover's quotient divides.
     pub fn zn_minus_one(&self) -> &L::LoadedScalar {
         &self.zn_minus_one
     }
@@ -262,6 +265,79 @@ impl<F: Clone> Expression<F> {
     }
 }
 
+impl<F: PrimeField> Expression<F> {
+    /// Evaluates the expression with `loader`, given the common polynomials,
+    /// evaluations of queried polynomials and `challenges`. Linear parts are
+    /// accumulated and fused into a single `sum_with_coeff_and_const` when
+    /// multiplied or returned, and products by constants are folded into
+    /// coefficients.
+    pub fn evaluate_loaded<L: ScalarLoader<F>>(
+        &self,
+        loader: &L,
+        common_poly: &impl Fn(CommonPolynomial) -> L::LoadedScalar,
+        evaluations: &HashMap<Query, L::LoadedScalar>,
+        challenges: &[L::LoadedScalar],
+    ) -> Result<L::LoadedScalar, Error> {
+        type Linear<F, T> = (Vec<(F, T)>, F);
+
+        let variable = |value: L::LoadedScalar| (vec![(F::one(), value)], F::zero());
+        let scale = |(terms, constant): Linear<F, L::LoadedScalar>, scalar: F| {
+            (
+                terms
+                    .into_iter()
+                    .map(|(coeff, value)| (coeff * scalar, value))
+                    .collect_vec(),
+                constant * scalar,
+            )
+        };
+        let fuse = |(terms, constant): Linear<F, L::LoadedScalar>| match terms.as_slice() {
+            [(coeff, value)] if *coeff == F::one() && constant == F::zero() => value.clone(),
+            _ => loader.sum_with_coeff_and_const(
+                &terms
+                    .iter()
+                    .map(|(coeff, value)| (*coeff, value))
+                    .collect_vec(),
+                constant,
+            ),
+        };
+
+        let linear = self.evaluate(
+            &|scalar| Ok((Vec::new(), scalar)),
+            &|poly| Ok(variable(common_poly(poly))),
+            &|query| {
+                evaluations
+                    .get(&query)
+                    .cloned()
+                    .map(variable)
+                    .ok_or(Error::InvalidQuery(query))
+            },
+            &|index| {
+                challenges
+                    .get(index)
+                    .cloned()
+                    .map(variable)
+                    .ok_or(Error::InvalidChallenge(index))
+            },
+            &|a| Ok(scale(a?, -F::one())),
+            &|a, b| {
+                let ((mut terms, lhs), (rhs_terms, rhs)) = (a?, b?);
+                terms.extend(rhs_terms);
+                Ok((terms, lhs + rhs))
+            },
+            &|a, b| {
+                let (a, b) = (a?, b?);
+                Ok(match (a.0.is_empty(), b.0.is_empty()) {
+                    (true, _) => scale(b, a.1),
+                    (_, true) => scale(a, b.1),
+                    _ => variable(fuse(a) * &fuse(b)),
+                })
+            },
+            &|a, scalar| Ok(scale(a?, scalar)),
+        )?;
+        Ok(fuse(linear))
+    }
+}
+
 impl<F: Clone> From<Query> for Expression<F> {
     fn from(query: Query) -> Self {
         Self::Polynomial(query)
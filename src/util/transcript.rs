@@ -1,8 +1,44 @@
 use crate::{
     loader::{native::NativeLoader, Loader},
-    {util::arithmetic::CurveAffine, Error},
+    {
+        util::arithmetic::{fe_to_fe, CurveAffine, Field},
+        Error,
+    },
 };
 
+/// Canonical layout in which an elliptic curve point is absorbed into a
+/// transcript that works over the scalar field, which is the x-coordinate
+/// followed by the y-coordinate, each reduced into the scalar field. Returns
+/// `None` for the identity.
+pub fn ec_point_to_native<C: CurveAffine>(ec_point: &C) -> Option<[C::Scalar; 2]> {
+    Option::from(ec_point.coordinates()).map(|coordinates| {
+        [coordinates.x(), coordinates.y()].map(|coordinate| fe_to_fe(*coordinate))
+    })
+}
+
+/// Whether transcripts accept the identity point, which has no affine
+/// coordinates, for absorption.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StrictMode {
+    /// Identity is rejected with an error.
+    #[default]
+    Strict,
+    /// Identity is absorbed as all-zero coordinates.
+    Lenient,
+}
+
+/// Same as [`ec_point_to_native`] but encoding the identity as all-zero
+/// coordinates when `mode` is [`StrictMode::Lenient`].
+pub fn ec_point_to_native_with_mode<C: CurveAffine>(
+    ec_point: &C,
+    mode: StrictMode,
+) -> Option<[C::Scalar; 2]> {
+    ec_point_to_native(ec_point).or_else(|| {
+        (mode == StrictMode::Lenient && bool::from(ec_point.is_identity()))
+            .then(|| [C::Scalar::zero(); 2])
+    })
+}
+
 pub trait Transcript<C, L>
 where
     C: CurveAffine,
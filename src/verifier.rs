@@ -8,7 +8,7 @@ use std::fmt::Debug;
 
 mod plonk;
 
-pub use plonk::{Plonk, PlonkProof};
+pub use plonk::{Milestone, Plonk, PlonkProof, VerifierTrace};
 
 pub trait PlonkVerifier<C, L, MOS>
 where
@@ -45,6 +45,55 @@ where
         instances: &[Vec<L::LoadedScalar>],
         proof: &Self::Proof,
     ) -> Result<Vec<MOS::Accumulator>, Error> {
+        Self::succinct_verify_traced(svk, protocol, instances, proof, None)
+    }
+}
+
+impl<MOS, AE> Plonk<MOS, AE> {
+    /// Same as [`PlonkVerifier::succinct_verify`] but also returns the named
+    /// intermediate values computed along the way.
+    pub fn succinct_verify_with_trace<C, L>(
+        svk: &MOS::SuccinctVerifyingKey,
+        protocol: &Protocol<C>,
+        instances: &[Vec<L::LoadedScalar>],
+        proof: &PlonkProof<C, L, MOS>,
+    ) -> Result<(Vec<MOS::Accumulator>, VerifierTrace<C, L, MOS>), Error>
+    where
+        C: CurveAffine,
+        L: Loader<C>,
+        MOS: MultiOpenScheme<C, L>,
+    {
+        let mut trace = VerifierTrace::default();
+        let accumulators =
+            Self::succinct_verify_traced(svk, protocol, instances, proof, Some(&mut trace))?;
+        Ok((accumulators, trace))
+    }
+
+    fn succinct_verify_traced<C, L>(
+        svk: &MOS::SuccinctVerifyingKey,
+        protocol: &Protocol<C>,
+        instances: &[Vec<L::LoadedScalar>],
+        proof: &PlonkProof<C, L, MOS>,
+        mut trace: Option<&mut VerifierTrace<C, L, MOS>>,
+    ) -> Result<Vec<MOS::Accumulator>, Error>
+    where
+        C: CurveAffine,
+        L: Loader<C>,
+        MOS: MultiOpenScheme<C, L>,
+    {
+        if let Some(trace) = trace.as_mut() {
+            for (idx, witness) in proof.witnesses.iter().enumerate() {
+                trace.push_ec_point(format!("witness commitment {idx}"), witness);
+            }
+            for (idx, challenge) in proof.challenges.iter().enumerate() {
+                trace.push_scalar(format!("challenge {idx}"), challenge);
+            }
+            for (idx, quotient) in proof.quotients.iter().enumerate() {
+                trace.push_ec_point(format!("quotient commitment {idx}"), quotient);
+            }
+            trace.push_scalar("z", &proof.z);
+        }
+
         let common_poly_eval = {
             let mut common_poly_eval = CommonPolynomialEvaluation::new(
                 &protocol.domain,
@@ -60,9 +109,22 @@ where
 
         let mut evaluations = proof.evaluations(protocol, instances, &common_poly_eval)?;
         let commitments = proof.commitments(protocol, &common_poly_eval, &mut evaluations)?;
+        if let Some(trace) = trace.as_mut() {
+            let quotient_query = Query::new(
+                protocol.preprocessed.len() + protocol.num_instance.len() + proof.witnesses.len(),
+                Rotation::cur(),
+            );
+            trace.push_scalar("quotient evaluation", &evaluations[&quotient_query]);
+        }
         let queries = proof.queries(protocol, evaluations);
 
         let accumulator = MOS::succinct_verify(svk, &commitments, &proof.z, &queries, &proof.pcs)?;
+        if let Some(trace) = trace.as_mut() {
+            trace.push_accumulator("opening accumulator", &accumulator);
+            for (idx, old_accumulator) in proof.old_accumulators.iter().enumerate() {
+                trace.push_accumulator(format!("old accumulator {idx}"), old_accumulator);
+            }
+        }
 
         let accumulators = iter::empty()
             .chain(Some(accumulator))
@@ -73,6 +135,80 @@ where
     }
 }
 
+/// Value recorded at a named step of verification.
+#[derive(Clone, Debug)]
+pub enum Milestone<C, L, MOS>
+where
+    C: CurveAffine,
+    L: Loader<C>,
+    MOS: MultiOpenScheme<C, L>,
+{
+    Scalar(L::LoadedScalar),
+    EcPoint(L::LoadedEcPoint),
+    Accumulator(MOS::Accumulator),
+}
+
+/// Named intermediate values of a verification in the order they are
+/// computed, to be compared step by step against a reference run.
+#[derive(Clone, Debug)]
+pub struct VerifierTrace<C, L, MOS>
+where
+    C: CurveAffine,
+    L: Loader<C>,
+    MOS: MultiOpenScheme<C, L>,
+{
+    pub milestones: Vec<(String, Milestone<C, L, MOS>)>,
+}
+
+impl<C, L, MOS> Default for VerifierTrace<C, L, MOS>
+where
+    C: CurveAffine,
+    L: Loader<C>,
+    MOS: MultiOpenScheme<C, L>,
+{
+    fn default() -> Self {
+        Self {
+            milestones: Vec::new(),
+        }
+    }
+}
+
+impl<C, L, MOS> VerifierTrace<C, L, MOS>
+where
+    C: CurveAffine,
+    L: Loader<C>,
+    MOS: MultiOpenScheme<C, L>,
+{
+    pub fn names(&self) -> Vec<&str> {
+        self.milestones
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Milestone<C, L, MOS>> {
+        self.milestones
+            .iter()
+            .find(|(exist, _)| exist == name)
+            .map(|(_, milestone)| milestone)
+    }
+
+    fn push_scalar(&mut self, name: impl Into<String>, scalar: &L::LoadedScalar) {
+        self.milestones
+            .push((name.into(), Milestone::Scalar(scalar.clone())));
+    }
+
+    fn push_ec_point(&mut self, name: impl Into<String>, ec_point: &L::LoadedEcPoint) {
+        self.milestones
+            .push((name.into(), Milestone::EcPoint(ec_point.clone())));
+    }
+
+    fn push_accumulator(&mut self, name: impl Into<String>, accumulator: &MOS::Accumulator) {
+        self.milestones
+            .push((name.into(), Milestone::Accumulator(accumulator.clone())));
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PlonkProof<C, L, MOS>
 where
@@ -420,7 +556,13 @@ where
             let queries = PlonkProof::<C, NativeLoader, MOS>::empty_queries(protocol);
             MOS::estimate_cost(&queries)
         };
-        plonk_cost + pcs_cost
+        // Verifier's terms are folded into the first msm of the scheme
+        let num_msm = plonk_cost.num_msm;
+        let mut cost = plonk_cost + pcs_cost;
+        if let Some(arity) = cost.msm_arities.first_mut() {
+            *arity += num_msm;
+        }
+        cost
     }
 }
 